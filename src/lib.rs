@@ -1,84 +1,2362 @@
 use itertools::Itertools;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::ops::Range;
 use wasm_bindgen::prelude::*;
 
-#[derive(PartialEq, Clone, Copy)]
-struct Location {
+mod render;
+
+#[cfg(feature = "serde")]
+mod report;
+
+pub use render::{render_svg, render_svg_with_grid_lines, RenderError};
+
+#[cfg(feature = "image")]
+pub use render::render_png;
+
+#[cfg(feature = "serde")]
+pub use report::{result_json, validate_json, validate_structured, Report};
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub struct Location {
     x: usize,
     y: usize,
 }
 
-impl Location {
-    pub fn new(x: usize, y: usize) -> Self {
-        Location {x, y}
-    }
+impl Location {
+    /// The (x, y) offsets to the 8 surrounding cells, in order of y and then x.
+    pub const DIRECTIONS: [(isize, isize); 8] = [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)];
+    
+    pub fn new(x: usize, y: usize) -> Self {
+        Location {x, y}
+    }
+    
+    /// The location moved by the given offsets, or `None` if either coordinate would be negative.
+    pub fn offset(&self, dx: isize, dy: isize) -> Option<Location> {
+        Some(Location::new(self.x.checked_add_signed(dx)?, self.y.checked_add_signed(dy)?))
+    }
+    
+    pub fn x(&self) -> usize {
+        self.x
+    }
+    
+    pub fn y(&self) -> usize {
+        self.y
+    }
+    
+    /// The straight line distance between the centres of two cells.
+    pub fn euclidean_distance(&self, other: &Location) -> f64 {
+        let (x_offset, y_offset) = (self.x.abs_diff(other.x), self.y.abs_diff(other.y));
+        
+        ((x_offset * x_offset + y_offset * y_offset) as f64).sqrt()
+    }
+    
+    fn manhattan_distance(&self, &other: &Location) -> usize {
+        (self.x).abs_diff(other.x) + (self.y).abs_diff(other.y)
+    }
+    
+    fn chebyshev_distance(&self, &other: &Location) -> usize {
+        (self.x).abs_diff(other.x).max((self.y).abs_diff(other.y))
+    }
+}
+
+/// Which neighbouring cells the escape path may step to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Connectivity {
+    /// Only the 4 cells sharing an edge.
+    #[default]
+    Orthogonal,
+    /// All 8 surrounding cells, so a path can leak through a diagonal gap.
+    Diagonal,
+}
+
+impl Connectivity {
+    fn adjacent(&self, a: &Location, b: &Location) -> bool {
+        match self {
+            Connectivity::Orthogonal => a.manhattan_distance(b) == 1,
+            Connectivity::Diagonal => a.chebyshev_distance(b) == 1,
+        }
+    }
+}
+
+/// A cell reached by the escape path search, with the cell it was reached from.
+#[derive(Debug)]
+pub struct PathStep {
+    location: Location,
+    parent: Option<Location>,
+    distance: usize,
+    estimate: usize,
+}
+
+impl PathStep {
+    pub fn new(location: Location, parent: Option<Location>, distance: usize, heuristic: usize) -> Self {
+        PathStep {
+            location,
+            parent,
+            distance,
+            estimate: distance + heuristic,
+        }
+    }
+    
+    /// The cell reached.
+    pub fn location(&self) -> Location {
+        self.location
+    }
+    
+    /// The cell this one was reached from, or `None` for a cell the search started from.
+    pub fn parent(&self) -> Option<Location> {
+        self.parent
+    }
+    
+    /// The number of steps from the start of the search.
+    pub fn distance(&self) -> usize {
+        self.distance
+    }
+}
+
+// Ordered so that a max-heap pops the smallest estimated total distance first, breaking
+// ties by popping the smallest y and then the smallest x first. This makes the escape path
+// the same on every run.
+impl Ord for PathStep {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.cmp(&self.estimate)
+            .then(other.location.y.cmp(&self.location.y))
+            .then(other.location.x.cmp(&self.location.x))
+    }
+}
+
+impl PartialOrd for PathStep {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for PathStep {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for PathStep {}
+
+/// How distance from the centre is measured when deciding which cells must be background.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DistanceMetric {
+    /// Straight line distance, which gives a round circle.
+    #[default]
+    Euclidean,
+    /// The number of orthogonal steps, which gives a diamond.
+    Manhattan,
+    /// The number of steps when diagonal steps are allowed, which gives a square.
+    Chebyshev,
+}
+
+impl DistanceMetric {
+    fn distance(&self, dx: f64, dy: f64) -> f64 {
+        let (dx, dy) = (dx.abs(), dy.abs());
+        
+        match self {
+            DistanceMetric::Euclidean => dx.hypot(dy),
+            DistanceMetric::Manhattan => dx + dy,
+            DistanceMetric::Chebyshev => dx.max(dy),
+        }
+    }
+}
+
+/// How far inside and outside the ideal radius the ring may be drawn. Cells closer to the centre
+/// than `radius - inner`, or further from it than `radius + outer`, must be background.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RingTolerance {
+    pub inner: f64,
+    pub outer: f64,
+}
+
+impl Default for RingTolerance {
+    fn default() -> Self {
+        RingTolerance { inner: 1.0, outer: 1.0 }
+    }
+}
+
+/// Background characters for either side of the ring, for circles whose inside is filled with a
+/// different character from the outside.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BackgroundPair {
+    pub inner: char,
+    pub outer: char,
+}
+
+/// Whether the inside of the ring is background or part of the drawn shape.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CircleKind {
+    /// Only the ring is foreground, and every cell inside it must be background.
+    #[default]
+    Outline,
+    /// Every cell inside the ring must be foreground too.
+    Filled,
+}
+
+/// Where the circle sits within the square.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BorderPolicy {
+    /// The ring touches the middle of each side of the square.
+    #[default]
+    Flush,
+    /// Every cell on the outer border must be background, and the ring touches the middle of
+    /// each side of the square just inside it. The radius is one less than for `Flush`.
+    Margin,
+    /// Every cell on the outer border must be this character, which is neither background nor
+    /// foreground, and the ring touches the middle of each side of the square just inside it.
+    /// Positions are still reported in the full square, frame included.
+    Frame(char),
+}
+
+/// How the background character is identified.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BackgroundStrategy {
+    /// The character at the centre of the square.
+    #[default]
+    Centre,
+    /// The most common character among the cells outside the ring, such as the corners, or the
+    /// character at the centre if the square is too small to have any. Filled disks always use
+    /// this, since their centre is part of the drawn shape rather than the background. A filled
+    /// disk too small to have any cells outside the ring takes the other character instead.
+    Perimeter,
+}
+
+impl BackgroundStrategy {
+    fn background(&self, s: &str) -> Option<char> {
+        match self {
+            BackgroundStrategy::Centre => background_character(s),
+            BackgroundStrategy::Perimeter => perimeter_character(s),
+        }
+    }
+    
+    fn grid_background(&self, grid: &Grid) -> char {
+        let r = grid.size / 2;
+        
+        match self {
+            BackgroundStrategy::Centre => grid.cells[r][r],
+            // A grid is never empty, so it always has a centre character.
+            BackgroundStrategy::Perimeter => perimeter_background(grid.positions(), grid.size, CircleKind::Outline).unwrap(),
+        }
+    }
+}
+
+/// The number of lines in the input and the range of their widths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Dimensions {
+    pub height: usize,
+    pub min_width: usize,
+    pub max_width: usize,
+}
+
+impl Dimensions {
+    pub fn is_square(&self) -> bool {
+        self.height == self.max_width && self.min_width == self.max_width
+    }
+}
+
+impl fmt::Display for Dimensions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Dimensions { height, min_width, max_width } = self;
+        
+        if min_width == max_width {
+            write!(f, "The height is {height} but the width is {max_width}.")
+        } else {
+            write!(f, "The height is {height} but the widths range from {min_width} to {max_width}.")
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationResult {
+    Valid { radius: usize, foreground: char, background: char },
+    Empty,
+    NotSquare { dimensions: Dimensions, jagged_rows: Vec<(usize, usize)> },
+    NotOdd,
+    RadiusTooSmall(usize),
+    TooFewCharacters(usize),
+    TooManyCharacters(usize),
+    UnexpectedCharacter { character: char, position: (usize, usize) },
+    CentreNotBackground,
+    MissingBackground { background: char, positions: Vec<(usize, usize)> },
+    MissingForeground { foreground: char, positions: Vec<(usize, usize)> },
+    /// `radius` is the radius of the circle itself, which is smaller than half the diagram when
+    /// the circle sits inside a margin or frame.
+    EscapePath { path: Vec<Location>, exit: Location, diagram: Vec<String>, radius: usize },
+}
+
+/// The order in which the two parts of a reported position are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CoordinateOrder {
+    /// Column then row, as `(x, y)`.
+    #[default]
+    XY,
+    /// Row then column, as `(row, column)`.
+    RowCol,
+}
+
+/// Where reported positions are counted from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Indexing {
+    /// The top left cell is (0, 0).
+    #[default]
+    ZeroBased,
+    /// The top left cell is (1, 1), as in most text editors.
+    OneBased,
+}
+
+impl CoordinateOrder {
+    fn label(&self) -> &'static str {
+        match self {
+            CoordinateOrder::XY => "(x, y)",
+            CoordinateOrder::RowCol => "(row, column)",
+        }
+    }
+    
+    fn arrange(&self, (x, y): (usize, usize)) -> (usize, usize) {
+        match self {
+            CoordinateOrder::XY => (x, y),
+            CoordinateOrder::RowCol => (y, x),
+        }
+    }
+}
+
+/// How line breaks and diagrams are written in a description.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DiagramFormat {
+    /// `<br>` line breaks, with diagrams wrapped in `<code>`, for display on a web page.
+    #[default]
+    Html,
+    /// `\n` line breaks with no markup, for display in a terminal.
+    PlainText,
+    /// `\n` line breaks, with diagrams framed in a box titled with the radius, for display in a
+    /// terminal.
+    BoxedText,
+}
+
+impl DiagramFormat {
+    fn line_break(&self) -> &'static str {
+        match self {
+            DiagramFormat::Html => "<br>",
+            DiagramFormat::PlainText | DiagramFormat::BoxedText => "\n",
+        }
+    }
+    
+    /// The rows of a diagram of a circle of the given radius, joined and marked up for display
+    /// in this format. The radius is only used for the title of a box.
+    pub fn diagram(&self, rows: &[String], radius: usize) -> String {
+        let diagram = rows.join(self.line_break());
+        
+        match self {
+            DiagramFormat::Html => format!("<code>{diagram}</code>"),
+            DiagramFormat::PlainText => diagram,
+            DiagramFormat::BoxedText => boxed(rows, radius),
+        }
+    }
+}
+
+impl ValidationResult {
+    pub fn is_valid(&self) -> bool {
+        matches!(self, ValidationResult::Valid { .. })
+    }
+    
+    // Whether the input failed before its shape could be checked, so it isn't an odd square of 2
+    // distinct characters.
+    fn is_malformed(&self) -> bool {
+        matches!(
+            self,
+            ValidationResult::Empty
+                | ValidationResult::NotSquare { .. }
+                | ValidationResult::NotOdd
+                | ValidationResult::RadiusTooSmall(_)
+                | ValidationResult::TooFewCharacters(_)
+                | ValidationResult::TooManyCharacters(_)
+                | ValidationResult::UnexpectedCharacter { .. }
+        )
+    }
+    
+    /// The cells of the escape path in order from inside the circle to the outside, if there is one.
+    /// The path is deterministic: where several paths are equally short, cells with smaller y and
+    /// then smaller x are preferred.
+    pub fn escape_path(&self) -> Option<&[Location]> {
+        match self {
+            ValidationResult::EscapePath { path, .. } => Some(path),
+            _ => None,
+        }
+    }
+    
+    /// The compass direction of the first step of the escape path, such as `"east"`, if there is
+    /// one. This points from the centre towards the gap the path leaves through.
+    pub fn first_escape_direction(&self) -> Option<&'static str> {
+        match self.escape_path()? {
+            [from, to, ..] => Some(compass_direction(from, to)),
+            _ => None,
+        }
+    }
+    
+    pub fn reason(&self) -> &'static str {
+        match self {
+            ValidationResult::Valid { .. } => "valid",
+            ValidationResult::Empty => "empty",
+            ValidationResult::NotSquare { .. } => "not_square",
+            ValidationResult::NotOdd => "not_odd",
+            ValidationResult::RadiusTooSmall(_) => "radius_too_small",
+            ValidationResult::TooFewCharacters(_) => "too_few_characters",
+            ValidationResult::TooManyCharacters(_) => "too_many_characters",
+            ValidationResult::UnexpectedCharacter { .. } => "unexpected_character",
+            ValidationResult::CentreNotBackground => "centre_not_background",
+            ValidationResult::MissingBackground { .. } => "missing_background",
+            ValidationResult::MissingForeground { .. } => "missing_foreground",
+            ValidationResult::EscapePath { .. } => "escape_path",
+        }
+    }
+    
+    /// The number of positions listed in the result, if it lists any.
+    pub fn position_count(&self) -> Option<usize> {
+        match self {
+            ValidationResult::MissingBackground { positions, .. } | ValidationResult::MissingForeground { positions, .. } => Some(positions.len()),
+            ValidationResult::NotSquare { jagged_rows, .. } => Some(jagged_rows.len()),
+            ValidationResult::UnexpectedCharacter { .. } => Some(1),
+            _ => None,
+        }
+    }
+    
+    /// The byte offset into `s` of each position listed in the result, for results that list
+    /// positions of characters. `s` must be the input the result came from.
+    pub fn byte_offsets(&self, s: &str) -> Vec<usize> {
+        let positions = match self {
+            ValidationResult::MissingBackground { positions, .. } | ValidationResult::MissingForeground { positions, .. } => positions.clone(),
+            ValidationResult::UnexpectedCharacter { position, .. } => vec![*position],
+            _ => vec![],
+        };
+        
+        positions.into_iter().filter_map(|(x, y)| byte_offset(s, x, y)).collect()
+    }
+    
+    /// The same result with every reported position counted from (1, 1) rather than (0, 0).
+    /// This includes the row numbers of rows with the wrong width, the path and its exit.
+    pub fn one_based(&self) -> ValidationResult {
+        let shift = |&(x, y): &(usize, usize)| (x + 1, y + 1);
+        let shift_location = |l: &Location| Location::new(l.x + 1, l.y + 1);
+        
+        match self {
+            ValidationResult::NotSquare { dimensions, jagged_rows } => ValidationResult::NotSquare {
+                dimensions: *dimensions,
+                jagged_rows: jagged_rows.iter().map(|&(row, width)| (row + 1, width)).collect(),
+            },
+            ValidationResult::UnexpectedCharacter { character, position } => ValidationResult::UnexpectedCharacter { character: *character, position: shift(position) },
+            ValidationResult::MissingBackground { background, positions } => ValidationResult::MissingBackground { background: *background, positions: positions.iter().map(shift).collect() },
+            ValidationResult::MissingForeground { foreground, positions } => ValidationResult::MissingForeground { foreground: *foreground, positions: positions.iter().map(shift).collect() },
+            ValidationResult::EscapePath { path, exit, diagram, radius } => ValidationResult::EscapePath {
+                path: path.iter().map(shift_location).collect(),
+                exit: shift_location(exit),
+                diagram: diagram.clone(),
+                radius: *radius,
+            },
+            result => result.clone(),
+        }
+    }
+    
+    /// A description of the result. Lists of positions are cut short after `max_reported`
+    /// entries, if given, with a note of how many more there are. Positions, including row
+    /// numbers, are counted from (1, 1) rather than (0, 0) with [`Indexing::OneBased`].
+    pub fn describe(&self, order: CoordinateOrder, format: DiagramFormat, max_reported: Option<usize>, indexing: Indexing) -> String {
+        let line_break = format.line_break();
+        let shifted;
+        let (result, base) = match indexing {
+            Indexing::ZeroBased => (self, 0),
+            Indexing::OneBased => {
+                shifted = self.one_based();
+                (&shifted, 1)
+            }
+        };
+        
+        match result {
+            ValidationResult::Valid { radius, .. } => format!("This is a valid text circle of radius {radius}."),
+            ValidationResult::Empty => "Invalid. The input is empty.".to_string(),
+            ValidationResult::NotSquare { dimensions, jagged_rows } if jagged_rows.is_empty() => format!("Invalid. The input is not square. {dimensions}"),
+            ValidationResult::NotSquare { dimensions, jagged_rows } => {
+                let formatted_jagged_rows = separated_tuples(jagged_rows, CoordinateOrder::XY, format, max_reported);
+                format!("Invalid. The input is not square. {dimensions} The following rows (row, width) differ from the most common width:{line_break}{formatted_jagged_rows}")
+            }
+            ValidationResult::NotOdd => "Invalid. The side length of the square is not odd.".to_string(),
+            ValidationResult::RadiusTooSmall(_) => "Invalid. Radius too small to define a circle.".to_string(),
+            ValidationResult::TooFewCharacters(_) => "Invalid. The input contains only 1 distinct character, but a circle needs a foreground and a background character.".to_string(),
+            ValidationResult::TooManyCharacters(count) => format!("Invalid. The input contains {count} distinct characters, but a circle should only have 2."),
+            ValidationResult::UnexpectedCharacter { character, position } => {
+                let label = order.label();
+                let (a, b) = order.arrange(*position);
+                format!("Invalid. The character {character:?} at position {label} ({a}, {b}) from ({base}, {base}) at left top is neither the background nor the foreground character.")
+            }
+            ValidationResult::CentreNotBackground => "Invalid. The centre is not background.".to_string(),
+            ValidationResult::MissingBackground { background, positions } => {
+                let label = order.label();
+                let formatted_missing_background = separated_tuples(positions, order, format, max_reported);
+                format!("Invalid. The following positions {label} from ({base}, {base}) at left top should be background character \"{background}\":{line_break}{formatted_missing_background}")
+            }
+            ValidationResult::MissingForeground { foreground, positions } => {
+                let label = order.label();
+                let formatted_missing_foreground = separated_tuples(positions, order, format, max_reported);
+                format!("Invalid. The following positions {label} from ({base}, {base}) at left top should be foreground character \"{foreground}\":{line_break}{formatted_missing_foreground}")
+            }
+            ValidationResult::EscapePath { diagram, radius, .. } => {
+                let path = format.diagram(diagram, *radius);
+                format!("Invalid. There should not be a path from inside the circle to outside:{line_break}{line_break}{path}")
+            }
+        }
+    }
+}
+
+impl fmt::Display for ValidationResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.describe(CoordinateOrder::default(), DiagramFormat::default(), None, Indexing::default()))
+    }
+}
+
+/// The reason an input could not be used as a valid text circle.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError(pub ValidationResult);
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0.describe(CoordinateOrder::default(), DiagramFormat::PlainText, None, Indexing::default()))
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+impl ValidationResult {
+    /// The radius of a valid circle, or the result as an error if it is not valid.
+    pub fn into_radius(self) -> Result<usize, ValidationError> {
+        match self {
+            ValidationResult::Valid { radius, .. } => Ok(radius),
+            invalid => Err(ValidationError(invalid)),
+        }
+    }
+}
+
+/// The reason text cannot be read as a square of characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    Empty,
+    /// The rows are not all the same width. `jagged_rows` holds the row index and width of each
+    /// row that differs from the most common width.
+    RaggedRows { dimensions: Dimensions, jagged_rows: Vec<(usize, usize)> },
+    /// The rows are all the same width, but not as wide as the input is tall.
+    NotSquare(Dimensions),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "The input is empty."),
+            ParseError::RaggedRows { dimensions, .. } => write!(f, "The rows are not all the same width. {dimensions}"),
+            ParseError::NotSquare(dimensions) => write!(f, "The input is not square. {dimensions}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl From<ParseError> for ValidationResult {
+    fn from(error: ParseError) -> Self {
+        match error {
+            ParseError::Empty => ValidationResult::Empty,
+            ParseError::RaggedRows { dimensions, jagged_rows } => ValidationResult::NotSquare { dimensions, jagged_rows },
+            ParseError::NotSquare(dimensions) => ValidationResult::NotSquare { dimensions, jagged_rows: vec![] },
+        }
+    }
+}
+
+/// A square of characters read from text by [`parse`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid {
+    cells: Vec<Vec<char>>,
+    size: usize,
+}
+
+/// Reads text as a square of characters, checking its structure once so that later steps can
+/// rely on it. Trailing newlines are ignored.
+pub fn parse(s: &str) -> Result<Grid, ParseError> {
+    let normalized;
+    let s = if s.contains('\r') {
+        normalized = normalize_line_endings(s);
+        normalized.as_str()
+    } else {
+        s
+    };
+    
+    parse_rows(&lines(s))
+}
+
+/// The input with `\r\n` line endings changed to `\n` and any other `\r` removed, so that a
+/// file with mixed line endings has the same rows and widths as one with `\n` throughout. This
+/// is applied before parsing.
+pub fn normalize_line_endings(s: &str) -> String {
+    s.replace("\r\n", "\n").replace('\r', "")
+}
+
+fn parse_rows(rows: &[&str]) -> Result<Grid, ParseError> {
+    let widths = rows.iter().copied().map(width).collect::<Vec<usize>>();
+    let size = square_size(&widths)?;
+    
+    Ok(Grid { cells: rows.iter().map(|row| row.chars().collect()).collect(), size })
+}
+
+// Each byte is one cell, so no UTF-8 decoding is needed to find the widths.
+fn parse_byte_rows(rows: &[&[u8]]) -> Result<Grid, ParseError> {
+    let widths = rows.iter().map(|row| row.len()).collect::<Vec<usize>>();
+    let size = square_size(&widths)?;
+    
+    Ok(Grid { cells: rows.iter().map(|row| row.iter().map(|&b| b as char).collect()).collect(), size })
+}
+
+fn square_size(widths: &[usize]) -> Result<usize, ParseError> {
+    if widths.is_empty() {
+        return Err(ParseError::Empty);
+    }
+    
+    let dimensions = dimensions(widths);
+    
+    if dimensions.min_width != dimensions.max_width {
+        return Err(ParseError::RaggedRows { dimensions, jagged_rows: rows_differing_from_modal_width(widths) });
+    }
+    
+    if !dimensions.is_square() {
+        return Err(ParseError::NotSquare(dimensions));
+    }
+    
+    Ok(dimensions.height)
+}
+
+// The checks on the size of the input that come before its characters are looked at: it must be
+// a square with an odd side, unless even sides are allowed, and a radius of at least
+// `MIN_RADIUS`. Gives the side length of the square.
+fn checked_size(widths: &[usize], allow_even: bool) -> Result<usize, ValidationResult> {
+    let size = square_size(widths)?;
+    
+    if size % 2 != 1 && !allow_even {
+        return Err(ValidationResult::NotOdd);
+    }
+    
+    let inner_radius = whole_radius(size);
+    
+    if inner_radius < MIN_RADIUS {
+        return Err(ValidationResult::RadiusTooSmall(inner_radius));
+    }
+    
+    Ok(size)
+}
+
+impl Grid {
+    /// The side length of the square.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+    
+    /// The character at column `x` of row `y`, or `None` if the position is outside the grid.
+    pub fn at(&self, x: usize, y: usize) -> Option<char> {
+        self.cells.get(y)?.get(x).copied()
+    }
+    
+    fn widths(&self) -> Vec<usize> {
+        self.cells.iter().map(Vec::len).collect()
+    }
+    
+    fn positions(&self) -> impl Iterator<Item = (usize, usize, char)> + '_ {
+        self.cells.iter().enumerate().flat_map(|(y, row)| row.iter().enumerate().map(move |(x, &c)| (x, y, c)))
+    }
+    
+    fn distinct_characters(&self) -> Vec<char> {
+        self.cells.iter().flatten().copied().unique().collect()
+    }
+    
+    fn first_unexpected_character(&self, backgrounds: &[char]) -> Option<(usize, usize, char)> {
+        let foreground = self.positions()
+            .map(|(_, _, c)| c)
+            .filter(|c| !backgrounds.contains(c))
+            .counts()
+            .into_iter()
+            .max_by_key(|&(c, count)| (count, c))
+            .map(|(c, _)| c);
+        
+        self.positions().find(|&(_, _, c)| c.is_control() || (!backgrounds.contains(&c) && Some(c) != foreground))
+    }
+    
+    fn region(&self, top_left: Location, size: usize) -> Grid {
+        let cells = self.cells[top_left.y..top_left.y + size].iter().map(|row| row[top_left.x..top_left.x + size].to_vec()).collect();
+        
+        Grid { cells, size }
+    }
+    
+    fn foreground_mask(&self, background: char) -> Vec<Vec<bool>> {
+        self.cells.iter().map(|row| row.iter().map(|&c| c != background).collect()).collect()
+    }
+}
+
+/// Settings for [`validate_with`]. The defaults give the same results as [`validate`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ValidatorOptions {
+    /// Which neighbouring cells the escape path may step to.
+    pub connectivity: Connectivity,
+    /// How the background character is identified.
+    pub background: BackgroundStrategy,
+    /// Allow any number of foreground characters alongside the single background character, for
+    /// example to anti-alias the edge of the circle.
+    pub allow_extra_foreground: bool,
+    /// The character the escape path diagram is drawn with, or `None` to use a character not
+    /// used in the input.
+    pub paving: Option<char>,
+    /// How far from the ideal radius the ring may be drawn.
+    pub tolerance: RingTolerance,
+    /// Pad short rows with the background character, so that a circle with trailing background
+    /// stripped by an editor is still square. This only happens when the longest row is exactly
+    /// as wide as the input is tall.
+    pub pad_rows: bool,
+    /// Treat upper and lower case versions of a letter as the same character. Characters are
+    /// then reported in lower case.
+    pub case_insensitive: bool,
+    /// Allow further rings inside the outermost one. Only the cells outside the outermost ring
+    /// must then be background.
+    pub allow_concentric: bool,
+    /// Report the first character that is neither the background nor the most common foreground
+    /// character, or that is a control character such as a tab. This takes precedence over
+    /// `allow_extra_foreground`.
+    pub strict: bool,
+    /// Whether the circle is drawn flush to the edges or inside a border of background.
+    pub border: BorderPolicy,
+    /// Report an outline whose centre is not the background character as such, rather than
+    /// listing the centre with any other missing background. This only has an effect with
+    /// [`BackgroundStrategy::Perimeter`], since otherwise the centre is the background by
+    /// definition.
+    pub require_background_centre: bool,
+    /// Expand each tab to spaces up to the next multiple of this many columns before checking
+    /// the input is square, or 0 to leave tabs as they are.
+    pub tab_width: usize,
+    /// Require one background character inside the ring and another outside it, instead of a
+    /// single background character. Cells within the ring that are not foreground may be
+    /// either. This takes precedence over `background` and `strict`.
+    pub split_background: Option<BackgroundPair>,
+    /// Whether the inside of the ring must be background or foreground.
+    pub kind: CircleKind,
+    /// Ignore lines starting with this character, such as `'#'` for a title, when they come
+    /// before the first row of the circle. Stripping stops at the first line that could be a row
+    /// of the circle: one as wide as the number of lines from it to the end, made only of
+    /// characters that appear in the lines below it. Rows of the circle are then kept even if
+    /// they start with the prefix. Positions are counted in the input with the comment lines
+    /// removed.
+    pub comment_prefix: Option<char>,
+    /// Validate squares with an even side length instead of reporting them as not odd. The
+    /// centre is then the corner shared by the middle 2 by 2 block of cells, and the reported
+    /// radius is half the side length.
+    pub allow_even: bool,
+    /// How distance from the centre is measured, for circles drawn as diamonds or squares.
+    pub metric: DistanceMetric,
+}
+
+impl ValidatorOptions {
+    fn background_strategy(&self) -> BackgroundStrategy {
+        match self.kind {
+            CircleKind::Outline => self.background,
+            CircleKind::Filled => BackgroundStrategy::Perimeter,
+        }
+    }
+    
+    fn background(&self, s: &str) -> Option<char> {
+        match self.kind {
+            CircleKind::Outline => self.background.background(s),
+            CircleKind::Filled => perimeter_background(cells(s), height(s), CircleKind::Filled),
+        }
+    }
+    
+    fn grid_background(&self, grid: &Grid) -> char {
+        match self.kind {
+            CircleKind::Outline => self.background.grid_background(grid),
+            // A grid is never empty, so it always has a centre character.
+            CircleKind::Filled => perimeter_background(grid.positions(), grid.size, CircleKind::Filled).unwrap(),
+        }
+    }
+}
+
+#[wasm_bindgen]
+pub fn validate_text_circle(s: &str) -> String {
+    validate_with(s, &ValidatorOptions::default()).to_string()
+}
+
+/// Validates a text circle. Trailing newlines are ignored, so only blank lines within the
+/// input cause it to be reported as not square.
+pub fn validate(s: &str) -> ValidationResult {
+    validate_with(s, &ValidatorOptions::default())
+}
+
+/// Validates a text circle with the given settings.
+pub fn validate_with(s: &str, options: &ValidatorOptions) -> ValidationResult {
+    let normalized;
+    let s = if s.contains('\r') {
+        normalized = normalize_line_endings(s);
+        normalized.as_str()
+    } else {
+        s
+    };
+    
+    let uncommented;
+    let s = match options.comment_prefix {
+        Some(prefix) => {
+            uncommented = strip_comment_lines(s, prefix);
+            uncommented.as_str()
+        }
+        None => s,
+    };
+    
+    let expanded;
+    let s = if options.tab_width > 0 && s.contains('\t') {
+        expanded = expand_tabs(s, options.tab_width);
+        expanded.as_str()
+    } else {
+        s
+    };
+    
+    let s = trim_trailing_newlines(s);
+    
+    let folded;
+    let s = if options.case_insensitive {
+        folded = s.chars().map(lower_case).collect::<String>();
+        folded.as_str()
+    } else {
+        s
+    };
+    
+    let padded;
+    let s = match options.background(s) {
+        Some(background) if options.pad_rows && dimensions(&line_widths(s)).max_width == height(s) => {
+            padded = padded_rows(s, background);
+            padded.as_str()
+        }
+        _ => s,
+    };
+    
+    match parse(s) {
+        Ok(grid) => validate_parsed(&grid, options),
+        Err(error) => error.into(),
+    }
+}
+
+/// Validates a text circle given as its rows, without joining them into a single string.
+pub fn validate_rows(rows: &[&str]) -> ValidationResult {
+    match parse_rows(rows) {
+        Ok(grid) => validate_parsed(&grid, &ValidatorOptions::default()),
+        Err(error) => error.into(),
+    }
+}
+
+/// Validates a text circle given as rows of bytes, with each byte as one cell. This is meant for
+/// ASCII input, and bytes above 127 are read as the Latin-1 character with that value rather
+/// than as UTF-8.
+pub fn validate_bytes(rows: &[&[u8]]) -> ValidationResult {
+    match parse_byte_rows(rows) {
+        Ok(grid) => validate_parsed(&grid, &ValidatorOptions::default()),
+        Err(error) => error.into(),
+    }
+}
+
+/// Validates a text circle read one line at a time, giving the same result as [`validate`] on the
+/// joined lines. Lines should not include their line endings, and trailing empty lines are
+/// ignored. This is a convenience for input that arrives as lines, not a streaming validator:
+/// the escape path search needs the whole square, so a grid of one flag per cell is built from
+/// the lines before the shape is checked. It only saves keeping every character.
+pub fn validate_lines<I>(lines: I) -> ValidationResult
+where
+    I: IntoIterator,
+    I::Item: AsRef<str>,
+{
+    let mut widths = vec![];
+    let mut distinct = HashSet::new();
+    let mut first_character = None;
+    let mut matches_first = vec![];
+    
+    for line in lines {
+        let row = line.as_ref().chars().map(|c| {
+            distinct.insert(c);
+            c == *first_character.get_or_insert(c)
+        }).collect::<Vec<bool>>();
+        
+        widths.push(row.len());
+        
+        // Input with more than 2 characters can't reach the shape checks, so there is no need
+        // to keep its rows.
+        if distinct.len() <= 2 {
+            matches_first.push(row);
+        }
+    }
+    
+    while widths.last() == Some(&0) {
+        widths.pop();
+    }
+    
+    matches_first.truncate(widths.len());
+    
+    let size = match checked_size(&widths, false) {
+        Ok(size) => size,
+        Err(issue) => return issue,
+    };
+    
+    if distinct.len() < 2 {
+        return ValidationResult::TooFewCharacters(distinct.len());
+    }
+    
+    if distinct.len() > 2 {
+        return ValidationResult::TooManyCharacters(distinct.len());
+    }
+    
+    let r = size / 2;
+    let first_character = first_character.unwrap();
+    let second_character = distinct.into_iter().find(|&c| c != first_character).unwrap();
+    let centre_matches_first = matches_first[r][r];
+    let (background, foreground_character) = if centre_matches_first { (first_character, second_character) } else { (second_character, first_character) };
+    let foreground = matches_first.into_iter()
+        .map(|row| row.into_iter().map(|matches| matches != centre_matches_first).collect())
+        .collect::<Vec<Vec<bool>>>();
+    
+    validate_shape(&foreground, false, &ValidatorOptions::default(), background, foreground_character, |path| {
+        let cells = foreground.iter().map(|row| row.iter().map(|&f| if f { foreground_character } else { background }).collect()).collect();
+        
+        path_diagram(path, &Grid { cells, size }, None)
+    })
+}
+
+fn validate_parsed(grid: &Grid, options: &ValidatorOptions) -> ValidationResult {
+    if let Err(issue) = checked_size(&grid.widths(), options.allow_even) {
+        return issue;
+    }
+    
+    match options.border {
+        BorderPolicy::Flush => {}
+        BorderPolicy::Margin => return validate_region(grid, Location::new(1, 1), grid.size - 2, options),
+        BorderPolicy::Frame(frame) => {
+            let h = grid.size;
+            
+            if let Some((x, y, character)) = grid.positions().find(|&(x, y, c)| edge_square(&Location::new(x, y), h) && c != frame) {
+                return ValidationResult::UnexpectedCharacter { character, position: (x, y) };
+            }
+            
+            return validate_within(grid, &grid.region(Location::new(1, 1), h - 2), Location::new(1, 1), options);
+        }
+    }
+    
+    if let Some(pair) = options.split_background {
+        return validate_split_background(grid, pair, options);
+    }
+    
+    if options.strict {
+        let background = options.grid_background(grid);
+        
+        if let Some((x, y, character)) = grid.first_unexpected_character(&[background]) {
+            return ValidationResult::UnexpectedCharacter { character, position: (x, y) };
+        }
+    }
+    
+    let distinct = grid.distinct_characters();
+    
+    if distinct.len() < 2 {
+        return ValidationResult::TooFewCharacters(distinct.len());
+    }
+    
+    if distinct.len() > 2 && !options.allow_extra_foreground {
+        return ValidationResult::TooManyCharacters(distinct.len());
+    }
+    
+    let background = options.grid_background(grid);
+    let r = grid.size / 2;
+    let filled = options.kind == CircleKind::Filled;
+    
+    if !filled && options.require_background_centre && grid.cells[r][r] != background {
+        return ValidationResult::CentreNotBackground;
+    }
+    
+    let foreground_character = distinct.into_iter().find(|&c| c != background).unwrap();
+    let foreground = grid.foreground_mask(background);
+    
+    validate_shape(&foreground, filled, options, background, foreground_character, |path| path_diagram(path, grid, options.paving))
+}
+
+// Each side of the ring is checked for its own background character, so a cell that holds the
+// other side's background counts as missing background just as foreground there does. The rest
+// only depends on which cells are foreground.
+fn validate_split_background(grid: &Grid, pair: BackgroundPair, options: &ValidatorOptions) -> ValidationResult {
+    let backgrounds = [pair.inner, pair.outer];
+    
+    if !options.allow_extra_foreground {
+        if let Some((x, y, character)) = grid.first_unexpected_character(&backgrounds) {
+            return ValidationResult::UnexpectedCharacter { character, position: (x, y) };
+        }
+    }
+    
+    let misplaced = |background: char, on_this_side: &dyn Fn(usize, usize) -> bool| {
+        grid.positions()
+            .filter(|&(x, y, c)| on_this_side(x, y) && c != background)
+            .map(|(x, y, _)| (x, y))
+            .collect::<Vec<(usize, usize)>>()
+    };
+    let sides = [
+        (pair.inner, misplaced(pair.inner, &|x, y| inside_ring(x, y, grid.size, options.tolerance.inner, options.metric))),
+        (pair.outer, misplaced(pair.outer, &|x, y| outside_ring(x, y, grid.size, options.tolerance.outer, options.metric))),
+    ];
+    
+    for (background, positions) in sides {
+        if !positions.is_empty() {
+            return ValidationResult::MissingBackground { background, positions };
+        }
+    }
+    
+    let foreground = grid.cells.iter().map(|row| row.iter().map(|c| !backgrounds.contains(c)).collect()).collect::<Vec<Vec<bool>>>();
+    // With no foreground at all, the missing ring is reported in a character not used in the
+    // input rather than in either background character.
+    let foreground_character = grid.positions().map(|(_, _, c)| c).find(|c| !backgrounds.contains(c)).unwrap_or_else(|| character_to_pave_with(grid));
+    
+    validate_shape(&foreground, false, options, pair.inner, foreground_character, |path| path_diagram(path, grid, options.paving))
+}
+
+/// Validates a circle of the given radius centred on (`cx`, `cy`) within a larger square, which
+/// must be background everywhere outside the circle's own square. Positions in the result are
+/// in the coordinates of the whole input. This is an error if the input is not square or the
+/// circle does not fit inside it.
+pub fn validate_with_centre(s: &str, cx: usize, cy: usize, radius: Radius) -> Result<ValidationResult, SeedError> {
+    let Radius(radius) = radius;
+    let grid = parse(s).map_err(|_| SeedError::NotSquare)?;
+    
+    if cx < radius || cy < radius || cx + radius >= grid.size || cy + radius >= grid.size {
+        return Err(SeedError::OutsideGrid);
+    }
+    
+    Ok(validate_region(&grid, Location::new(cx - radius, cy - radius), 2 * radius + 1, &ValidatorOptions::default()))
+}
+
+// Checks everything outside the square region is background before validating the region.
+fn validate_region(grid: &Grid, top_left: Location, size: usize, options: &ValidatorOptions) -> ValidationResult {
+    let region = grid.region(top_left, size);
+    let background = options.grid_background(&region);
+    let in_region = |x: usize, y: usize| (top_left.x..top_left.x + size).contains(&x) && (top_left.y..top_left.y + size).contains(&y);
+    let outside_foreground = grid.positions()
+        .filter(|&(x, y, c)| !in_region(x, y) && c != background)
+        .map(|(x, y, _)| (x, y))
+        .collect::<Vec<(usize, usize)>>();
+    
+    if !outside_foreground.is_empty() {
+        return ValidationResult::MissingBackground { background, positions: outside_foreground };
+    }
+    
+    validate_within(grid, &region, top_left, options)
+}
+
+// Validates the region as a flush circle, moving any reported positions back out to the full
+// square.
+fn validate_within(grid: &Grid, region: &Grid, top_left: Location, options: &ValidatorOptions) -> ValidationResult {
+    let shift = |(x, y): (usize, usize)| (x + top_left.x, y + top_left.y);
+    let shift_location = |l: Location| Location::new(l.x + top_left.x, l.y + top_left.y);
+    
+    match validate_parsed(region, &ValidatorOptions { border: BorderPolicy::Flush, ..*options }) {
+        ValidationResult::MissingBackground { background, positions } => ValidationResult::MissingBackground { background, positions: positions.into_iter().map(shift).collect() },
+        ValidationResult::MissingForeground { foreground, positions } => ValidationResult::MissingForeground { foreground, positions: positions.into_iter().map(shift).collect() },
+        ValidationResult::UnexpectedCharacter { character, position } => ValidationResult::UnexpectedCharacter { character, position: shift(position) },
+        ValidationResult::EscapePath { path, exit, radius, .. } => {
+            let path = path.into_iter().map(shift_location).collect::<Vec<Location>>();
+            let diagram = path_diagram(&path, grid, options.paving);
+            ValidationResult::EscapePath { path, exit: shift_location(exit), diagram, radius }
+        }
+        result => result,
+    }
+}
+
+/// Every problem that can be found with the input, in the order [`validate`] checks for them, or
+/// an empty list if it is a valid circle. Checks that depend on an earlier one are skipped when it
+/// fails: nothing more is checked if the input is not square, and the shape is not checked if the
+/// radius is too small or there is only 1 character. An even side length doesn't stop the shape
+/// checks, which then measure from the corner shared by the middle 2 by 2 block of cells and take
+/// the background from the cell just below and to the right of it.
+pub fn all_issues(s: &str) -> Vec<ValidationResult> {
+    let grid = match parse(s) {
+        Ok(grid) => grid,
+        Err(error) => return vec![error.into()],
+    };
+    let mut issues = vec![];
+    
+    if grid.size % 2 != 1 {
+        issues.push(ValidationResult::NotOdd);
+    }
+    
+    if let Err(issue) = checked_size(&grid.widths(), true) {
+        issues.push(issue);
+        return issues;
+    }
+    
+    let distinct = grid.distinct_characters();
+    
+    if distinct.len() < 2 {
+        issues.push(ValidationResult::TooFewCharacters(distinct.len()));
+        return issues;
+    }
+    
+    if distinct.len() > 2 {
+        issues.push(ValidationResult::TooManyCharacters(distinct.len()));
+    }
+    
+    let tolerance = RingTolerance::default();
+    let background = BackgroundStrategy::Centre.grid_background(&grid);
+    let foreground_character = distinct.into_iter().find(|&c| c != background).unwrap();
+    let foreground = grid.foreground_mask(background);
+    let missing_background = missing_background_characters(&foreground, false, tolerance, DistanceMetric::Euclidean);
+    let missing_foreground = missing_foreground_characters(&foreground, tolerance, DistanceMetric::Euclidean);
+    
+    if !missing_background.is_empty() {
+        issues.push(ValidationResult::MissingBackground { background, positions: missing_background });
+    }
+    
+    if !missing_foreground.is_empty() {
+        issues.push(ValidationResult::MissingForeground { foreground: foreground_character, positions: missing_foreground });
+    }
+    
+    if let Some(path) = path_out_of_circle(&foreground, Connectivity::default(), BackgroundStrategy::Centre, tolerance, DistanceMetric::Euclidean) {
+        let exit = *path.last().unwrap();
+        let diagram = path_diagram(&path, &grid, None);
+        issues.push(ValidationResult::EscapePath { path, exit, diagram, radius: grid.size / 2 });
+    }
+    
+    issues
+}
+
+/// A quick check of the structural prerequisites for a circle: the input is an odd square
+/// containing exactly 2 distinct characters. This does not search for an escape path.
+pub fn looks_like_circle(s: &str) -> bool {
+    let s = trim_trailing_newlines(s);
+    
+    dimensions(&line_widths(s)).is_square() && odd(s) && distinct_characters(s).len() == 2
+}
+
+/// Validates each block of lines separated by one or more empty lines, giving one result per
+/// block. Lines containing only spaces are not separators, since a space can be the background.
+pub fn validate_many(s: &str) -> Vec<ValidationResult> {
+    let lines = lines(s);
+    
+    lines.split(|line| line.is_empty())
+        .filter(|block| !block.is_empty())
+        .map(|block| validate(&block.join("\n")))
+        .collect()
+}
+
+/// Validates a circle given as rows of cells, where `true` is foreground and `false` is
+/// background. Positions and diagrams in the result show foreground as `#` and background as `.`.
+pub fn validate_grid(grid: &[Vec<bool>]) -> ValidationResult {
+    let widths = grid.iter().map(Vec::len).collect::<Vec<usize>>();
+    
+    if let Err(issue) = checked_size(&widths, false) {
+        return issue;
+    }
+    
+    let distinct_count = grid.iter().flatten().unique().count();
+    
+    if distinct_count < 2 {
+        return ValidationResult::TooFewCharacters(distinct_count);
+    }
+    
+    validate_shape(grid, false, &ValidatorOptions::default(), '.', '#', |path| {
+        let cells = grid.iter().map(|row| row.iter().map(|&f| if f { '#' } else { '.' }).collect()).collect();
+        
+        path_diagram(path, &Grid { cells, size: grid.len() }, None)
+    })
+}
+
+// The checks that only depend on which cells are foreground, once the input is known to be an
+// odd square containing both foreground and background.
+fn validate_shape(foreground: &[Vec<bool>], filled: bool, options: &ValidatorOptions, background_character: char, foreground_character: char, diagram: impl Fn(&[Location]) -> Vec<String>) -> ValidationResult {
+    let missing_background = missing_background_characters(foreground, filled || options.allow_concentric, options.tolerance, options.metric);
+    
+    if !missing_background.is_empty() {
+        return ValidationResult::MissingBackground { background: background_character, positions: missing_background };
+    }
+    
+    // A filled disk must have no holes inside the ring, unless they are the gaps between
+    // concentric rings around a filled centre.
+    let missing_foreground = if !filled {
+        missing_foreground_characters(foreground, options.tolerance, options.metric)
+    } else if options.allow_concentric {
+        vec![]
+    } else {
+        missing_fill_characters(foreground, options.tolerance, options.metric)
+    };
+    
+    if !missing_foreground.is_empty() {
+        return ValidationResult::MissingForeground { foreground: foreground_character, positions: missing_foreground };
+    }
+    
+    // An inner ring would block a path from the centre even if the outermost ring has a gap, so
+    // concentric rings are searched inwards from the edge instead.
+    let strategy = if options.allow_concentric { BackgroundStrategy::Perimeter } else { options.background_strategy() };
+    
+    match path_out_of_circle(foreground, options.connectivity, strategy, options.tolerance, options.metric) {
+        Some(path) => {
+            // Paths run from inside the circle to the edge, and always include the edge cell.
+            let exit = *path.last().unwrap();
+            let diagram = diagram(&path);
+            ValidationResult::EscapePath { path, exit, diagram, radius: foreground.len() / 2 }
+        }
+        None => ValidationResult::Valid { radius: foreground.len() / 2, foreground: foreground_character, background: background_character },
+    }
+}
+
+// The radius rounded down to a whole number of cells. The ring of an even sided square is half
+// a cell inside half the side length, so a 4 by 4 square has no cell inside its ring and is as
+// degenerate as a 3 by 3 one.
+fn whole_radius(size: usize) -> usize {
+    (size - 1) / 2
+}
+
+/// The smallest radius that can define a circle. With radius 1 the only cell that must be
+/// background inside the ring is the centre itself, so any 3 by 3 square would qualify.
+pub const MIN_RADIUS: usize = 2;
+
+/// The radius of a circle, as opposed to its side length of `2 * radius + 1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Radius(pub usize);
+
+impl Radius {
+    /// The radius, or `None` if it is below [`MIN_RADIUS`] and so cannot define a circle.
+    pub fn new(radius: usize) -> Option<Radius> {
+        (radius >= MIN_RADIUS).then_some(Radius(radius))
+    }
+    
+    pub fn side_length(&self) -> usize {
+        2 * self.0 + 1
+    }
+}
+
+/// The reason a cell cannot be used as the starting point of a search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeedError {
+    NotSquare,
+    OutsideGrid,
+    NotBackground,
+}
+
+impl fmt::Display for SeedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SeedError::NotSquare => write!(f, "The input is not square."),
+            SeedError::OutsideGrid => write!(f, "The starting position is outside the input."),
+            SeedError::NotBackground => write!(f, "The starting position is not the background character."),
+        }
+    }
+}
+
+impl std::error::Error for SeedError {}
+
+/// Whether a path of background characters leads from (x, y) to the edge of the square. The
+/// background character is the one at the centre, and (x, y) must hold it.
+pub fn reaches_edge_from(s: &str, x: usize, y: usize) -> Result<bool, SeedError> {
+    let grid = parse(s).map_err(|_| SeedError::NotSquare)?;
+    let background = BackgroundStrategy::Centre.grid_background(&grid);
+    let seed_character = grid.at(x, y).ok_or(SeedError::OutsideGrid)?;
+    
+    if seed_character != background {
+        return Err(SeedError::NotBackground);
+    }
+    
+    let foreground = grid.foreground_mask(background);
+    
+    Ok(reachable_cells(&foreground, Connectivity::default(), vec![Location::new(x, y)]).iter().any(|l| edge_square(l, grid.size)))
+}
+
+/// The background cells that can be reached from `seed` through other background cells,
+/// including `seed` itself. The background character is the one at the centre. Empty if the
+/// input is not square or `seed` is outside it or not background.
+pub fn reachable_background(s: &str, seed: Location, connectivity: Connectivity) -> HashSet<Location> {
+    let Ok(grid) = parse(s) else {
+        return HashSet::new();
+    };
+    
+    let background = BackgroundStrategy::Centre.grid_background(&grid);
+    
+    if grid.at(seed.x, seed.y) != Some(background) {
+        return HashSet::new();
+    }
+    
+    reachable_cells(&grid.foreground_mask(background), connectivity, vec![seed])
+}
+
+/// The path from the centre to the edge as compass directions, such as "From the centre go north,
+/// north, north east to reach the top edge.", for reading aloud instead of showing a diagram. This
+/// looks for a path even if the ring has other faults, and gives `None` if there is no path or the
+/// input is not a square large enough to hold a circle.
+pub fn describe_escape_path(s: &str) -> Option<String> {
+    let grid = parse(s).ok()?;
+    
+    if grid.size / 2 < MIN_RADIUS {
+        return None;
+    }
+    
+    let r = grid.size / 2;
+    let background = BackgroundStrategy::Centre.grid_background(&grid);
+    let path = path_out_of_circle_from(Location::new(r, r), &grid.foreground_mask(background), Connectivity::default())?;
+    let directions = path.iter().tuple_windows().map(|(from, to)| compass_direction(from, to)).join(", ");
+    let exit = path[path.len() - 1];
+    let edge = if exit.y == 0 {
+        "top"
+    } else if exit.y == grid.size - 1 {
+        "bottom"
+    } else if exit.x == 0 {
+        "left"
+    } else {
+        "right"
+    };
+    
+    Some(format!("From the centre go {directions} to reach the {edge} edge."))
+}
+
+/// The ring cells where the inside of the circle breaks through the wall, in row-major order.
+/// Each escape path found is cut where it crosses the fewest ring cells that would seal the
+/// circle, as [`suggest_seal`] finds them, and those cells on the path are recorded and treated
+/// as foreground before searching again, until no path is left. Empty if the input is not square
+/// or the radius is too small.
+pub fn wall_gaps(s: &str) -> Vec<Location> {
+    let Ok(grid) = parse(s) else {
+        return vec![];
+    };
+    
+    let r = grid.size / 2;
+    
+    if r < MIN_RADIUS {
+        return vec![];
+    }
+    
+    let mut foreground = grid.foreground_mask(BackgroundStrategy::Centre.grid_background(&grid));
+    let mut gaps = vec![];
+    
+    while let Some(path) = path_out_of_circle_from(Location::new(r, r), &foreground, Connectivity::default()) {
+        // A cut separates the centre from the edge, so it always crosses this path.
+        let cut = minimum_seal(&foreground);
+        
+        for l in path.into_iter().filter(|l| cut.contains(l)) {
+            foreground[l.y][l.x] = true;
+            gaps.push(l);
+        }
+    }
+    
+    gaps.sort_by_key(|l| (l.y, l.x));
+    gaps
+}
+
+/// The fewest ring cells to change from background to foreground so that the inside of the
+/// circle no longer reaches the edge, in row-major order. This is a minimum cut between the
+/// centre and the edge, so a gap is filled with as few cells as possible. Cells that must be
+/// background are never suggested. Empty if the circle is sealed, the input is not square or the
+/// radius is too small.
+pub fn suggest_seal(s: &str) -> Vec<Location> {
+    let Ok(grid) = parse(s) else {
+        return vec![];
+    };
+    
+    if grid.size / 2 < MIN_RADIUS {
+        return vec![];
+    }
+    
+    minimum_seal(&grid.foreground_mask(BackgroundStrategy::Centre.grid_background(&grid)))
+}
+
+// A minimum cut between the centre and the edge through the background cells, in row-major
+// order.
+fn minimum_seal(foreground: &[Vec<bool>]) -> Vec<Location> {
+    let h = foreground.len();
+    let r = h / 2;
+    let cell_count = h * h;
+    // More than the number of cells, so it is never part of a minimum cut.
+    let unlimited = cell_count + 1;
+    let (source, sink) = (2 * cell_count, 2 * cell_count + 1);
+    // Each background cell is split into an entrance and an exit joined by an edge of capacity 1,
+    // so that cutting that edge stands for filling the cell.
+    let entrance = |l: &Location| 2 * (l.y * h + l.x);
+    let exit = |l: &Location| 2 * (l.y * h + l.x) + 1;
+    let background_cells = || (0..h).flat_map(|y| (0..h).map(move |x| Location::new(x, y))).filter(|l| !foreground[l.y][l.x]);
+    let mut network = FlowNetwork::new(2 * cell_count + 2);
+    
+    for l in background_cells() {
+        let fillable = !required_background(l.x, l.y, h);
+        network.add_edge(entrance(&l), exit(&l), if fillable { 1 } else { unlimited });
+        
+        for n in neighbours(l, Connectivity::default()).into_iter().filter(|n| n.x < h && n.y < h && !foreground[n.y][n.x]) {
+            network.add_edge(exit(&l), entrance(&n), unlimited);
+        }
+        
+        if edge_square(&l, h) {
+            network.add_edge(exit(&l), sink, unlimited);
+        }
+    }
+    
+    network.add_edge(source, entrance(&Location::new(r, r)), unlimited);
+    
+    let reached = network.max_flow_source_side(source, sink);
+    
+    background_cells()
+        .filter(|l| reached[entrance(l)] && !reached[exit(l)])
+        .collect()
+}
+
+// A directed graph with a capacity on each edge, for finding a minimum cut. Each edge is stored
+// next to its reverse, which starts with no capacity, so edge `i ^ 1` is the reverse of edge `i`.
+struct FlowNetwork {
+    edges_from: Vec<Vec<usize>>,
+    to: Vec<usize>,
+    capacity: Vec<usize>,
+}
+
+impl FlowNetwork {
+    fn new(node_count: usize) -> FlowNetwork {
+        FlowNetwork { edges_from: vec![vec![]; node_count], to: vec![], capacity: vec![] }
+    }
+    
+    fn add_edge(&mut self, from: usize, to: usize, capacity: usize) {
+        for (from, to, capacity) in [(from, to, capacity), (to, from, 0)] {
+            self.edges_from[from].push(self.to.len());
+            self.to.push(to);
+            self.capacity.push(capacity);
+        }
+    }
+    
+    // Pushes as much flow as possible from the source to the sink along shortest augmenting
+    // paths, then returns which nodes the source can still reach. The edges leading out of that
+    // set form a minimum cut.
+    fn max_flow_source_side(&mut self, source: usize, sink: usize) -> Vec<bool> {
+        loop {
+            let mut reached_by = vec![None; self.edges_from.len()];
+            let mut reached = vec![false; self.edges_from.len()];
+            let mut to_check = VecDeque::from([source]);
+            reached[source] = true;
+            
+            while let Some(node) = to_check.pop_front() {
+                for &edge in &self.edges_from[node] {
+                    let next = self.to[edge];
+                    
+                    if self.capacity[edge] > 0 && !reached[next] {
+                        reached[next] = true;
+                        reached_by[next] = Some(edge);
+                        to_check.push_back(next);
+                    }
+                }
+            }
+            
+            if !reached[sink] {
+                return reached;
+            }
+            
+            let mut path = vec![];
+            let mut node = sink;
+            
+            while let Some(edge) = reached_by[node] {
+                path.push(edge);
+                node = self.to[edge ^ 1];
+            }
+            
+            let flow = path.iter().map(|&edge| self.capacity[edge]).min().unwrap();
+            
+            for edge in path {
+                self.capacity[edge] -= flow;
+                self.capacity[edge ^ 1] += flow;
+            }
+        }
+    }
+}
+
+/// The background cells on the edge of the square that can be reached from the centre, in
+/// row-major order. These are every exit the inside of the circle leaks out through, where an
+/// escape path only reports the nearest, and include any background edge cell connected to a
+/// gap around the outside of the ring. Empty if the input is not square or the radius is too
+/// small.
+pub fn porous_edges(s: &str) -> Vec<Location> {
+    let Ok(grid) = parse(s) else {
+        return vec![];
+    };
+    
+    let r = grid.size / 2;
+    
+    if r < MIN_RADIUS {
+        return vec![];
+    }
+    
+    let h = grid.size;
+    let background = BackgroundStrategy::Centre.grid_background(&grid);
+    let mut exits = reachable_cells(&grid.foreground_mask(background), Connectivity::default(), vec![Location::new(r, r)])
+        .into_iter()
+        .filter(|l| edge_square(l, h))
+        .collect::<Vec<Location>>();
+    
+    exits.sort_by_key(|l| (l.y, l.x));
+    exits
+}
+
+/// A parsed input with its result from [`validate`], kept so that single cell edits can be
+/// checked without repeating the whole escape path search.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationState {
+    grid: Grid,
+    result: ValidationResult,
+    // The background cells reachable from the centre. This is only kept for a valid circle,
+    // since any other result is found again in full after an edit.
+    reachable: Option<HashSet<Location>>,
+}
+
+impl ValidationState {
+    /// Parses and validates the input.
+    pub fn new(s: &str) -> Result<ValidationState, ParseError> {
+        Ok(ValidationState::from_grid(parse(s)?))
+    }
+    
+    fn from_grid(grid: Grid) -> ValidationState {
+        let result = validate_parsed(&grid, &ValidatorOptions::default());
+        let reachable = match result {
+            ValidationResult::Valid { background, .. } => {
+                let centre_background = centre_cells(grid.size).into_iter().filter(|l| grid.cells[l.y][l.x] == background).collect();
+                Some(reachable_cells(&grid.foreground_mask(background), Connectivity::default(), centre_background))
+            }
+            _ => None,
+        };
+        
+        ValidationState { grid, result, reachable }
+    }
+    
+    /// The result for the input as it is now.
+    pub fn result(&self) -> &ValidationResult {
+        &self.result
+    }
+    
+    /// The input as it is now.
+    pub fn grid(&self) -> &Grid {
+        &self.grid
+    }
+    
+    /// Changes the character at column `x` of row `y` and returns the new result. Panics if the
+    /// position is outside the square.
+    pub fn edit(&mut self, x: usize, y: usize, new_char: char) -> &ValidationResult {
+        if self.grid.cells[y][x] == new_char {
+            return &self.result;
+        }
+        
+        match self.incremental_result(x, y, new_char) {
+            Some((result, newly_reachable)) => {
+                self.grid.cells[y][x] = new_char;
+                self.reachable = match (&result, self.reachable.take()) {
+                    (ValidationResult::Valid { foreground, .. }, Some(mut reachable)) => {
+                        if new_char == *foreground {
+                            reachable.remove(&Location::new(x, y));
+                        }
+                        reachable.extend(newly_reachable);
+                        Some(reachable)
+                    }
+                    _ => None,
+                };
+                self.result = result;
+            }
+            None => {
+                let mut grid = self.grid.clone();
+                grid.cells[y][x] = new_char;
+                *self = ValidationState::from_grid(grid);
+            }
+        }
+        
+        &self.result
+    }
+    
+    // The result after an edit to a valid circle, with the background cells the edit newly joins
+    // to the centre, or `None` if the input must be validated again in full. Only the edited cell
+    // can become missing background or foreground, and only opening a cell next to the centre's
+    // region can lead to an escape path. When it does, the full search is left to find the same
+    // shortest path as `validate`.
+    fn incremental_result(&self, x: usize, y: usize, new_char: char) -> Option<(ValidationResult, Vec<Location>)> {
+        let ValidationResult::Valid { foreground, background, .. } = self.result else {
+            return None;
+        };
+        let reachable = self.reachable.as_ref()?;
+        let h = self.grid.size;
+        let edited = Location::new(x, y);
+        
+        if self.grid.cells[y][x] == new_char {
+            return Some((self.result.clone(), vec![]));
+        }
+        
+        // Editing the centre changes which character is the background.
+        if centre_cells(h).contains(&edited) || (new_char != foreground && new_char != background) {
+            return None;
+        }
+        
+        if new_char == foreground {
+            let result = if required_background(x, y, h) {
+                ValidationResult::MissingBackground { background, positions: vec![(x, y)] }
+            } else {
+                self.result.clone()
+            };
+            
+            return Some((result, vec![]));
+        }
+        
+        if required_foreground(x, y, h, RingTolerance::default(), DistanceMetric::Euclidean) {
+            return Some((ValidationResult::MissingForeground { foreground, positions: vec![(x, y)] }, vec![]));
+        }
+        
+        let is_reachable = |l: &Location| reachable.contains(l);
+        
+        if !neighbours(edited, Connectivity::default()).iter().filter(|n| n.x < h && n.y < h).any(is_reachable) {
+            return Some((self.result.clone(), vec![]));
+        }
+        
+        let mut newly_reachable = HashSet::from([edited]);
+        let mut to_check = vec![edited];
+        
+        while let Some(l) = to_check.pop() {
+            if edge_square(&l, h) {
+                return None;
+            }
+            
+            for n in neighbours(l, Connectivity::default()) {
+                if n.x < h && n.y < h && self.grid.cells[n.y][n.x] == background && !is_reachable(&n) && newly_reachable.insert(n) {
+                    to_check.push(n);
+                }
+            }
+        }
+        
+        Some((self.result.clone(), newly_reachable.into_iter().collect()))
+    }
+}
+
+/// The result of changing the character at column `x` of row `y` of a previously validated
+/// input, the same as [`validate`] would give for the edited input. For a valid circle this
+/// only searches the background the edit joins to the inside of the circle. Panics if the
+/// position is outside the square.
+pub fn revalidate_after_edit(prev: &ValidationState, x: usize, y: usize, new_char: char) -> ValidationResult {
+    prev.incremental_result(x, y, new_char).map(|(result, _)| result).unwrap_or_else(|| {
+        let mut grid = prev.grid.clone();
+        grid.cells[y][x] = new_char;
+        validate_parsed(&grid, &ValidatorOptions::default())
+    })
+}
+
+/// The cells in the order the escape path search took them from its queue, starting from the
+/// centre and ending at the edge if there is a way out. Empty if the input is not square or the
+/// radius is too small.
+pub fn escape_search_trace(s: &str) -> Vec<Location> {
+    let Ok(grid) = parse(s) else {
+        return vec![];
+    };
+    
+    if grid.size / 2 < MIN_RADIUS {
+        return vec![];
+    }
+    
+    let r = grid.size / 2;
+    let h = grid.size;
+    let background = BackgroundStrategy::Centre.grid_background(&grid);
+    let mut trace = vec![];
+    traced_background_path(&grid.foreground_mask(background), Connectivity::default(), vec![Location::new(r, r)], |l| edge_square(l, h), |l| distance_to_edge(l, h), |step| trace.push(step.location));
+    
+    trace
+}
+
+fn compass_direction(from: &Location, to: &Location) -> &'static str {
+    match (to.x.cmp(&from.x), to.y.cmp(&from.y)) {
+        (Ordering::Equal, Ordering::Less) => "north",
+        (Ordering::Greater, Ordering::Less) => "north east",
+        (Ordering::Greater, Ordering::Equal) => "east",
+        (Ordering::Greater, Ordering::Greater) => "south east",
+        (Ordering::Equal, Ordering::Greater) => "south",
+        (Ordering::Less, Ordering::Greater) => "south west",
+        (Ordering::Less, Ordering::Equal) => "west",
+        (Ordering::Less, Ordering::Less) => "north west",
+        (Ordering::Equal, Ordering::Equal) => unreachable!("consecutive cells of a path are distinct"),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GenerateError {
+    RadiusTooSmall,
+    SameCharacters,
+}
+
+impl fmt::Display for GenerateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GenerateError::RadiusTooSmall => write!(f, "A circle must have radius at least {MIN_RADIUS}."),
+            GenerateError::SameCharacters => write!(f, "The foreground and background characters must be different."),
+        }
+    }
+}
+
+impl std::error::Error for GenerateError {}
+
+/// A valid text circle with foreground on every cell that is not required to be background.
+pub fn generate_circle(radius: Radius, foreground: char, background: char) -> Result<String, GenerateError> {
+    generate_circle_with_metric(radius, foreground, background, DistanceMetric::Euclidean)
+}
+
+/// A circle as [`generate_circle`] gives, but with distance measured by `metric`. It is valid
+/// with the same metric set in [`ValidatorOptions`].
+pub fn generate_circle_with_metric(radius: Radius, foreground: char, background: char, metric: DistanceMetric) -> Result<String, GenerateError> {
+    let Radius(radius) = radius;
+    
+    if radius < MIN_RADIUS {
+        return Err(GenerateError::RadiusTooSmall);
+    }
+    
+    if foreground == background {
+        return Err(GenerateError::SameCharacters);
+    }
+    
+    let h = 2 * radius + 1;
+    let RingTolerance { inner, outer } = RingTolerance::default();
+    let mut rows = (0..h).map(|y| {
+        (0..h).map(|x| if required_background_with(x, y, h, inner, outer, metric) { background } else { foreground }).collect::<String>()
+    });
+    
+    Ok(rows.join("\n"))
+}
+
+/// A valid circle of `#` on `.` with a radius picked from the range using the seed, so the same
+/// seed always gives the same circle. Radii below [`MIN_RADIUS`] are never picked, and a range
+/// with no radius of at least [`MIN_RADIUS`] panics.
+pub fn random_valid_circle(radius_range: Range<usize>, seed: u64) -> String {
+    let range = radius_range.start.max(MIN_RADIUS)..radius_range.end;
+    assert!(!range.is_empty(), "No radius of at least {MIN_RADIUS} in {radius_range:?}");
+    
+    let radius = range.start + (splitmix64(seed) % range.len() as u64) as usize;
+    
+    generate_circle(Radius(radius), '#', '.').unwrap()
+}
+
+// SplitMix64, which spreads consecutive seeds over the whole output range.
+fn splitmix64(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9e3779b97f4a7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    
+    z ^ (z >> 31)
+}
+
+/// The cells of a circle of the given radius that are not required to be background, in
+/// row-major order. These are the cells strictly between `radius - 1` and `radius + 1` from the
+/// centre.
+pub fn ideal_ring_cells(radius: Radius) -> Vec<Location> {
+    let h = radius.side_length();
+    
+    (0..h)
+        .cartesian_product(0..h)
+        .filter(|&(y, x)| !required_background(x, y, h))
+        .map(|(y, x)| Location::new(x, y))
+        .collect()
+}
+
+/// How the validator treats a cell, depending only on its position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellKind {
+    /// Inside the ring, so it must be background.
+    RequiredBackground,
+    /// Within the band the ring may be drawn in.
+    RingBand,
+    /// Outside the ring, so it must also be background.
+    Exterior,
+}
+
+/// The kind of each cell of a square input, in rows, using the radius given by [`radius`]. Only
+/// the number of lines in the input is used, not its characters.
+pub fn classification_grid(s: &str) -> Vec<Vec<CellKind>> {
+    let h = height(s);
+    let RingTolerance { inner, outer } = RingTolerance::default();
+    
+    (0..h)
+        .map(|y| {
+            (0..h)
+                .map(|x| {
+                    if inside_ring(x, y, h, inner, DistanceMetric::Euclidean) {
+                        CellKind::RequiredBackground
+                    } else if outside_ring(x, y, h, outer, DistanceMetric::Euclidean) {
+                        CellKind::Exterior
+                    } else {
+                        CellKind::RingBand
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// The sizes of an ideal circle of a given radius.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CircleMetrics {
+    /// The width and height of the square, `2 * radius + 1`.
+    pub side_length: usize,
+    /// The number of cells the ring may be drawn in, as listed by [`ideal_ring_cells`].
+    pub ring_cells: usize,
+    /// The number of cells inside the ring, which must be background.
+    pub interior_cells: usize,
+}
+
+/// The side length and cell counts of an ideal circle of the given radius.
+pub fn circle_metrics(radius: Radius) -> CircleMetrics {
+    let side_length = radius.side_length();
+    let RingTolerance { inner, .. } = RingTolerance::default();
+    let interior_cells = (0..side_length)
+        .cartesian_product(0..side_length)
+        .filter(|&(y, x)| inside_ring(x, y, side_length, inner, DistanceMetric::Euclidean))
+        .count();
+    
+    CircleMetrics { side_length, ring_cells: ideal_ring_cells(radius).len(), interior_cells }
+}
+
+/// The input with every position that should be background replaced with the background
+/// character. Input that is not an odd square of 2 distinct characters cannot be fixed and is
+/// returned as an error.
+pub fn autofix_background(s: &str) -> Result<String, ValidationError> {
+    let s = trim_trailing_newlines(s);
+    
+    let (background, positions) = match validate(s) {
+        ValidationResult::MissingBackground { background, positions } => (background, positions),
+        result if result.is_malformed() => return Err(ValidationError(result)),
+        _ => return Ok(s.to_string()),
+    };
+    
+    let positions = positions.into_iter().collect::<HashSet<(usize, usize)>>();
+    let rows = s.lines().enumerate().map(|(y, line)| {
+        line.chars().enumerate().map(|(x, c)| if positions.contains(&(x, y)) { background } else { c }).collect::<String>()
+    });
+    
+    Ok(rows.collect::<Vec<String>>().join("\n"))
+}
+
+/// How closely the input matches the ideal ring, from 0.0 to 1.0. This is the fraction of cells
+/// that match, out of every cell that is either on the ideal ring or drawn as foreground. Input
+/// that is not an odd square of 2 distinct characters cannot be scored and is returned as an error.
+pub fn circle_score(s: &str) -> Result<f64, ValidationError> {
+    let s = trim_trailing_newlines(s);
+    let result = validate(s);
     
-    fn manhattan_distance(&self, &other: &Location) -> usize {
-        (self.x).abs_diff(other.x) + (self.y).abs_diff(other.y)
+    if result.is_malformed() {
+        return Err(ValidationError(result));
     }
+    
+    let h = height(s);
+    let background = background_character(s).unwrap();
+    let compared = cells(s)
+        .map(|(x, y, c)| (!required_background(x, y, h), c != background))
+        .filter(|&(on_ring, is_foreground)| on_ring || is_foreground)
+        .collect::<Vec<(bool, bool)>>();
+    let matching = compared.iter().filter(|&&(on_ring, is_foreground)| on_ring && is_foreground).count();
+    
+    Ok(matching as f64 / compared.len() as f64)
 }
 
-struct PathStep {
-    location: Location,
-    parent: Option<Location>,
-    distance: usize,
+/// The mean distance of every foreground cell from the centre, for comparing the circle as drawn
+/// with the radius given by [`radius`]. A circle that bulges or is squashed in places gives a
+/// different value. Cells outside the ring band are counted too, since limiting the mean to the
+/// band would hide a squashed side drawn inside it. This is NaN if there is no foreground.
+pub fn best_fit_radius(s: &str) -> f64 {
+    let h = height(s);
+    let Some(background) = background_character(s) else {
+        return f64::NAN;
+    };
+    
+    let distances = cells(s)
+        .filter(|&(_, _, c)| c != background)
+        .map(|(x, y, _)| distance_from_centre(x, y, h, DistanceMetric::Euclidean))
+        .collect::<Vec<f64>>();
+    
+    distances.iter().sum::<f64>() / distances.len() as f64
 }
 
-impl PathStep {
-    pub fn new(location: Location, parent: Option<Location>, distance: usize) -> Self {
-        PathStep {
-            location,
-            parent,
-            distance,
+/// For each row, the first and last x of each run of foreground cells, left to right. Each row
+/// of a circle's outline has a run on the left and one on the right, except at the top and
+/// bottom where the ring crosses the row in a single run. Empty if the input is empty.
+pub fn row_spans(s: &str) -> Vec<Vec<(usize, usize)>> {
+    let Some(background) = background_character(s) else {
+        return vec![];
+    };
+    
+    lines(s)
+        .into_iter()
+        .map(|line| {
+            let mut spans: Vec<(usize, usize)> = vec![];
+            
+            for (x, _) in line.chars().enumerate().filter(|&(_, c)| c != background) {
+                match spans.last_mut() {
+                    Some((_, end)) if *end + 1 == x => *end = x,
+                    _ => spans.push((x, x)),
+                }
+            }
+            
+            spans
+        })
+        .collect()
+}
+
+/// The corners of the convex hull of the foreground cells, going anticlockwise on screen from the
+/// topmost of the leftmost cells. Cells along a straight edge between two corners are not
+/// included, so a well drawn circle gives many corners spread evenly around it while a square
+/// gives 4. Empty if there is no foreground.
+pub fn foreground_hull(s: &str) -> Vec<Location> {
+    let Some(background) = background_character(s) else {
+        return vec![];
+    };
+    
+    let mut points = cells(s)
+        .filter(|&(_, _, c)| c != background)
+        .map(|(x, y, _)| Location::new(x, y))
+        .collect::<Vec<Location>>();
+    points.sort_by_key(|l| (l.x, l.y));
+    
+    if points.len() < 3 {
+        return points;
+    }
+    
+    // Andrew's monotone chain, building one half of the hull forwards and the other backwards.
+    // A corner is kept only where the chain turns, which drops collinear cells.
+    let mut hull: Vec<Location> = vec![];
+    
+    for pass in [points.clone(), points.into_iter().rev().collect()] {
+        let start = hull.len();
+        
+        for l in pass {
+            while hull.len() >= start + 2 && cross(hull[hull.len() - 2], hull[hull.len() - 1], l) >= 0 {
+                hull.pop();
+            }
+            hull.push(l);
         }
+        
+        // The last point of each half is the first point of the other.
+        hull.pop();
+    }
+    
+    hull
+}
+
+// Positive where the turn from a through b to c is clockwise on screen, since y increases
+// downwards.
+fn cross(a: Location, b: Location, c: Location) -> isize {
+    let (ax, ay, bx, by, cx, cy) = (a.x as isize, a.y as isize, b.x as isize, b.y as isize, c.x as isize, c.y as isize);
+    
+    (bx - ax) * (cy - ay) - (by - ay) * (cx - ax)
+}
+
+/// The input with each cell that should be background but is foreground, and each cell of the
+/// ideal ring that is background, marked with a character not used in the input. Input that is
+/// not an odd square of 2 distinct characters cannot be compared and is returned as an error.
+pub fn deviation_diagram(s: &str, format: DiagramFormat) -> Result<String, ValidationError> {
+    let result = validate(s);
+    
+    if result.is_malformed() {
+        return Err(ValidationError(result));
     }
+    
+    let grid = parse(s).map_err(|error| ValidationError(error.into()))?;
+    let mut markers = unused_characters(&grid);
+    let (background_marker, foreground_marker) = (markers.next().unwrap(), markers.next().unwrap());
+    
+    let rows = deviations(&grid)
+        .map(|(_, _, c, deviation)| match deviation {
+            Some(Deviation::ShouldBeBackground) => background_marker,
+            Some(Deviation::ShouldBeForeground) => foreground_marker,
+            None => c,
+        })
+        .chunks(grid.size)
+        .into_iter()
+        .map(|row| row.collect::<String>())
+        .collect::<Vec<String>>();
+    let line_break = format.line_break();
+    
+    Ok(format!("Cells marked {background_marker} should be background and cells marked {foreground_marker} should be foreground:{line_break}{line_break}{}", format.diagram(&rows, grid.size / 2)))
 }
 
+/// The cells that differ from the ideal circle as a flat list of `[x, y, kind, ...]`, where kind
+/// is 0 for a cell that should be background and 1 for a cell of the ideal ring that should be
+/// foreground. Empty if the input is not an odd square of 2 distinct characters.
 #[wasm_bindgen]
-pub fn validate_text_circle(s: &str) -> String {
-    if s.len() == 0 {
-        return "Invalid. The input is empty.".to_string();
+pub fn deviation_cells(s: &str) -> Vec<u32> {
+    if validate(s).is_malformed() {
+        return vec![];
     }
+    
+    let Ok(grid) = parse(s) else {
+        return vec![];
+    };
+    
+    deviations(&grid)
+        .filter_map(|(x, y, _, deviation)| Some([x as u32, y as u32, deviation? as u32]))
+        .flatten()
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Deviation {
+    ShouldBeBackground = 0,
+    ShouldBeForeground = 1,
+}
+
+// Each cell with how it differs from the ideal circle, if it does. Cells of the ideal ring are
+// exactly those that are not required to be background.
+fn deviations(grid: &Grid) -> impl Iterator<Item = (usize, usize, char, Option<Deviation>)> + '_ {
+    let background = BackgroundStrategy::Centre.grid_background(grid);
+    
+    grid.positions().map(move |(x, y, c)| {
+        let deviation = match (c != background, required_background(x, y, grid.size)) {
+            (true, true) => Some(Deviation::ShouldBeBackground),
+            (false, false) => Some(Deviation::ShouldBeForeground),
+            _ => None,
+        };
+        
+        (x, y, c, deviation)
+    })
+}
+
+/// The input turned a quarter turn clockwise. Trailing newlines are ignored.
+pub fn rotate_90(s: &str) -> String {
+    let rows = trim_trailing_newlines(s).lines().map(|line| line.chars().collect()).collect::<Vec<Vec<char>>>();
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    
+    (0..width)
+        .map(|x| rows.iter().rev().filter_map(|row| row.get(x)).collect::<String>())
+        .join("\n")
+}
+
+/// The smallest square around the foreground, so that a circle surrounded by extra background
+/// becomes a circle that touches the middle of each side. The background is taken to be the most
+/// common character. A non-square bounding box is widened equally on both sides, using
+/// background beyond the edges of the input. Input with no foreground is returned unchanged.
+pub fn crop_to_circle(s: &str) -> String {
+    let s = trim_trailing_newlines(s);
+    let Some(&(background, _)) = character_histogram(s).first() else {
+        return s.to_string();
+    };
+    let foreground_cells = cells(s).filter(|&(_, _, c)| c != background).collect::<Vec<(usize, usize, char)>>();
+    let Some((left, right)) = foreground_cells.iter().map(|&(x, _, _)| x).minmax().into_option() else {
+        return s.to_string();
+    };
+    let (top, bottom) = foreground_cells.iter().map(|&(_, y, _)| y).minmax().into_option().unwrap();
+    let (width, height) = (right - left + 1, bottom - top + 1);
+    let side = width.max(height);
+    
+    // Offsets are signed, since widening a box at the edge of the input can go past it.
+    let left = left as isize - ((side - width) / 2) as isize;
+    let top = top as isize - ((side - height) / 2) as isize;
+    let rows = s.lines().map(|line| line.chars().collect()).collect::<Vec<Vec<char>>>();
+    
+    (top..top + side as isize)
+        .map(|y| {
+            (left..left + side as isize)
+                .map(|x| if x < 0 || y < 0 { None } else { rows.get(y as usize).and_then(|row| row.get(x as usize)) })
+                .map(|c| c.copied().unwrap_or(background))
+                .collect::<String>()
+        })
+        .join("\n")
+}
 
-    if !square(s) {
-        return "Invalid. The input is not square.".to_string();
+/// The circle drawn with `#` on `.`, with `\n` line endings and no trailing newline, so that
+/// circles of the same shape give identical strings. A circle surrounded by extra background is
+/// cropped first. Input that is not a valid circle either way is returned as an error.
+pub fn canonicalize(s: &str) -> Result<String, ValidationError> {
+    let s = normalize_line_endings(s);
+    let s = trim_trailing_newlines(&s);
+    let cropped;
+    
+    let (s, foreground) = match validate(s) {
+        ValidationResult::Valid { foreground, .. } => (s, foreground),
+        result => {
+            cropped = crop_to_circle(s);
+            
+            match validate(&cropped) {
+                ValidationResult::Valid { foreground, .. } => (cropped.as_str(), foreground),
+                _ => return Err(ValidationError(result)),
+            }
+        }
+    };
+    
+    Ok(s.lines().map(|line| line.chars().map(|c| if c == foreground { '#' } else { '.' }).collect::<String>()).join("\n"))
+}
+
+/// The input reflected left to right, so each row is reversed. Trailing newlines are ignored.
+pub fn mirror_horizontal(s: &str) -> String {
+    trim_trailing_newlines(s).lines().map(|line| line.chars().rev().collect::<String>()).join("\n")
+}
+
+/// The positions `(x, y)` of cells that differ from their mirror image across each axis of
+/// symmetry. This is advisory: a valid circle can still be drawn unevenly within its ring.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SymmetryReport {
+    /// Reflected left to right, across the vertical line through the centre.
+    pub vertical: Vec<(usize, usize)>,
+    /// Reflected top to bottom, across the horizontal line through the centre.
+    pub horizontal: Vec<(usize, usize)>,
+    /// Reflected across the diagonal from the top left to the bottom right.
+    pub diagonal: Vec<(usize, usize)>,
+}
+
+impl SymmetryReport {
+    pub fn is_symmetric(&self) -> bool {
+        self.vertical.is_empty() && self.horizontal.is_empty() && self.diagonal.is_empty()
     }
+}
+
+/// The cells of the input that are not symmetric about each axis. A cell whose mirror image is
+/// missing, because the input is not square, counts as a difference.
+pub fn symmetry_report(s: &str) -> SymmetryReport {
+    let rows = trim_trailing_newlines(s).lines().map(|line| line.chars().collect()).collect::<Vec<Vec<char>>>();
+    let h = rows.len();
+    let asymmetric_cells = |mirror: &dyn Fn(usize, usize) -> (usize, usize)| {
+        rows.iter()
+            .enumerate()
+            .flat_map(|(y, row)| row.iter().enumerate().map(move |(x, &c)| (x, y, c)))
+            .filter(|&(x, y, c)| {
+                let (mirror_x, mirror_y) = mirror(x, y);
+                
+                rows.get(mirror_y).and_then(|row| row.get(mirror_x)) != Some(&c)
+            })
+            .map(|(x, y, _)| (x, y))
+            .collect::<Vec<(usize, usize)>>()
+    };
     
-    if !odd(s) {
-        return "Invalid. The side length of the square is not odd.".to_string();
+    SymmetryReport {
+        vertical: asymmetric_cells(&|x, y| (rows[y].len() - 1 - x, y)),
+        horizontal: asymmetric_cells(&|x, y| (x, h - 1 - y)),
+        diagonal: asymmetric_cells(&|x, y| (y, x)),
+    }
+}
+
+/// The positions `(x, y)` of cells that differ between two inputs of the same size, such as a
+/// broken circle and its repaired version. Foreground is decided separately for each input, as
+/// any character other than the one at its centre.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DiffReport {
+    /// Background in the first input and foreground in the second.
+    pub added_foreground: Vec<(usize, usize)>,
+    /// Foreground in the first input and background in the second.
+    pub removed_foreground: Vec<(usize, usize)>,
+    /// Foreground or background in both, but drawn with a different character.
+    pub changed_character: Vec<(usize, usize)>,
+}
+
+impl DiffReport {
+    pub fn is_unchanged(&self) -> bool {
+        self.added_foreground.is_empty() && self.removed_foreground.is_empty() && self.changed_character.is_empty()
+    }
+}
+
+/// The reason two inputs cannot be compared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareError {
+    NotSquare,
+    DifferentSizes(usize, usize),
+}
+
+impl fmt::Display for CompareError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CompareError::NotSquare => write!(f, "Both inputs must be square."),
+            CompareError::DifferentSizes(a, b) => write!(f, "The inputs are different sizes: {a} by {a} and {b} by {b}."),
+        }
     }
+}
+
+impl std::error::Error for CompareError {}
+
+/// The cells that changed from the first input to the second, classified by whether they became
+/// foreground, stopped being foreground, or only changed character.
+pub fn compare(a: &str, b: &str) -> Result<DiffReport, CompareError> {
+    let (a, b) = (parse(a).map_err(|_| CompareError::NotSquare)?, parse(b).map_err(|_| CompareError::NotSquare)?);
     
-    if distinct_characters(s).len() != 2 {
-        return "Invalid. The input does not contain 2 distinct characters.".to_string();
+    if a.size != b.size {
+        return Err(CompareError::DifferentSizes(a.size, b.size));
     }
     
-    let missing_background = missing_background_characters(s);
+    let (a_background, b_background) = (BackgroundStrategy::Centre.grid_background(&a), BackgroundStrategy::Centre.grid_background(&b));
+    let mut report = DiffReport::default();
     
-    if missing_background.len() > 0 {
-        let background = background_character(s);
-        let formatted_missing_background = br_separated_tuples(&missing_background);
-        return format!("Invalid. The following positions (x, y) from (0, 0) at left top should be background character \"{background}\":<br>{formatted_missing_background}");
+    for ((x, y, a_character), (_, _, b_character)) in a.positions().zip(b.positions()) {
+        match (a_character != a_background, b_character != b_background) {
+            (false, true) => report.added_foreground.push((x, y)),
+            (true, false) => report.removed_foreground.push((x, y)),
+            _ if a_character != b_character => report.changed_character.push((x, y)),
+            _ => {}
+        }
     }
     
-    match path_out_of_circle(s) {
-        Some(path) => return format!("Invalid. There should not be a path from inside the circle to outside:<br><br><code>{path}</code>"),
-        None => {let r = radius(s); return format!("This is a valid text circle of radius {r}.")}
+    Ok(report)
+}
+
+fn trim_trailing_newlines(s: &str) -> &str {
+    s.trim_end_matches(['\n', '\r'])
+}
+
+// Characters with no single character lower case form are left as they are, so that every row
+// keeps its width.
+fn lower_case(c: char) -> char {
+    let mut lower = c.to_lowercase();
+    
+    match (lower.next(), lower.next()) {
+        (Some(l), None) => l,
+        _ => c,
     }
 }
 
+fn expand_tabs(s: &str, tab_width: usize) -> String {
+    s.split('\n')
+        .map(|line| {
+            let mut expanded = String::new();
+            let mut column = 0;
+            
+            for c in line.chars() {
+                let columns = if c == '\t' { tab_width - column % tab_width } else { 1 };
+                
+                if c == '\t' {
+                    expanded.extend(std::iter::repeat_n(' ', columns));
+                } else {
+                    expanded.push(c);
+                }
+                
+                column += columns;
+            }
+            
+            expanded
+        })
+        .join("\n")
+}
+
+fn padded_rows(s: &str, background: char) -> String {
+    let side = height(s);
+    
+    s.lines()
+        .map(|line| {
+            let padding = side.saturating_sub(width(line));
+            
+            line.chars().chain(std::iter::repeat_n(background, padding)).collect::<String>()
+        })
+        .join("\n")
+}
+
+// Trailing newlines are ignored here as well as in validation, so that every helper sees the
+// same rows as `validate` does.
 fn lines(s: &str) -> Vec<&str> {
-    s.lines().collect::<Vec<&str>>()
+    trim_trailing_newlines(s).lines().collect::<Vec<&str>>()
 }
 
-fn height(s: &str) -> usize {
+/// The number of lines in the input, which is the side length of a square input. Trailing
+/// newlines are ignored.
+///
+/// ```
+/// use text_circle_validator::height;
+///
+/// let circle = ".###.\n#...#\n#...#\n#...#\n.###.\n";
+/// assert_eq!(height(circle), 5);
+/// ```
+pub fn height(s: &str) -> usize {
     lines(s).len()
 }
 
-fn square(s: &str) -> bool {
-    let widths = s.lines().map(|l| l.chars().collect::<Vec<char>>().len());
-    let max_width = widths.clone().max().unwrap();
-    let min_width = widths.min().unwrap();
+// The lines before the circle that start with `prefix` removed. A line that could be the first
+// row of the circle ends the comments, so a circle whose rows start with the prefix is kept.
+fn strip_comment_lines(s: &str, prefix: char) -> String {
+    let lines = lines(s);
+    let could_be_first_row = |y: usize| {
+        let below = &lines[y + 1..];
+        
+        width(lines[y]) == lines.len() - y && lines[y].chars().all(|c| below.iter().any(|row| row.contains(c)))
+    };
+    let first_kept = (0..lines.len()).find(|&y| !lines[y].starts_with(prefix) || could_be_first_row(y)).unwrap_or(lines.len());
+    
+    lines[first_kept..].join("\n")
+}
+
+fn width(line: &str) -> usize {
+    line.chars().count()
+}
+
+// Widths are counted in chars rather than bytes, so multi-byte characters such as '●' each
+// count as a single cell.
+fn line_widths(s: &str) -> Vec<usize> {
+    lines(s).into_iter().map(width).collect()
+}
+
+/// A character that is drawn as part of the character before it, such as the variation
+/// selector in an emoji. The two look like one cell but are counted as two, so the row appears
+/// wider than it is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CombiningCharacter {
+    pub character: char,
+    /// The character it combines with, or `None` if it starts a line.
+    pub combines_with: Option<char>,
+    /// The (x, y) position of the combining character itself, counted in chars.
+    pub position: (usize, usize),
+}
+
+impl fmt::Display for CombiningCharacter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (x, y) = self.position;
+        let Some(previous) = self.combines_with else {
+            return write!(f, "The character {:?} at (x, y) ({x}, {y}) is a combining character at the start of a line.", self.character);
+        };
+        
+        write!(f, "The character {:?} at (x, y) ({x}, {y}) combines with {previous:?} to look like a single cell, but counts as 2. Use a character that is a single scalar value instead.", self.character)
+    }
+}
+
+/// Every character that combines with the one before it, in reading order. Any of these will
+/// make a row count as wider than it looks.
+pub fn combining_characters(s: &str) -> Vec<CombiningCharacter> {
+    s.lines()
+        .enumerate()
+        .flat_map(|(y, line)| {
+            let previous = std::iter::once(None).chain(line.chars().map(Some));
+            
+            line.chars()
+                .zip(previous)
+                .enumerate()
+                .filter(|&(_, (c, _))| is_combining(c))
+                .map(move |(x, (character, combines_with))| CombiningCharacter { character, combines_with, position: (x, y) })
+        })
+        .collect()
+}
+
+// The standard library has no table of Unicode properties, so this covers the ranges most
+// likely to turn up: combining marks, variation selectors, emoji skin tone modifiers and the
+// zero width joiner.
+fn is_combining(c: char) -> bool {
+    matches!(c,
+        '\u{0300}'..='\u{036F}'
+        | '\u{1AB0}'..='\u{1AFF}'
+        | '\u{1DC0}'..='\u{1DFF}'
+        | '\u{200D}'
+        | '\u{20D0}'..='\u{20FF}'
+        | '\u{FE00}'..='\u{FE0F}'
+        | '\u{FE20}'..='\u{FE2F}'
+        | '\u{1F3FB}'..='\u{1F3FF}'
+        | '\u{E0100}'..='\u{E01EF}'
+    )
+}
+
+fn dimensions(widths: &[usize]) -> Dimensions {
+    let max_width = widths.iter().copied().max().unwrap_or(0);
+    let min_width = widths.iter().copied().min().unwrap_or(0);
+    
+    Dimensions { height: widths.len(), min_width, max_width }
+}
+
+/// The (row, width) of every line whose width differs from the most common width.
+pub fn jagged_rows(s: &str) -> Vec<(usize, usize)> {
+    rows_differing_from_modal_width(&line_widths(s))
+}
+
+fn rows_differing_from_modal_width(widths: &[usize]) -> Vec<(usize, usize)> {
+    let Some(modal_width) = widths.iter().counts().into_iter().max_by_key(|&(w, count)| (count, w)).map(|(w, _)| *w) else {
+        return vec![];
+    };
     
-    height(s) == max_width && min_width == max_width
+    widths.iter().copied().enumerate().filter(|&(_, w)| w != modal_width).collect()
 }
 
 fn odd(s: &str) -> bool {
@@ -86,33 +2364,252 @@ fn odd(s: &str) -> bool {
 }
 
 fn distinct_characters(s: &str) -> Vec<char> {
-    s.replace("\n", "").chars().collect::<Vec<char>>().into_iter().unique().collect()
+    grid_characters(s).unique().collect()
+}
+
+// Line endings are skipped whether they are `\n` or `\r\n`, so they are never counted as a
+// character of the grid.
+fn grid_characters(s: &str) -> impl Iterator<Item = char> + '_ {
+    s.chars().filter(|&c| c != '\n' && c != '\r')
+}
+
+/// Each distinct character in the input with the number of times it occurs, most frequent first.
+/// Characters that occur equally often are in character order.
+pub fn character_histogram(s: &str) -> Vec<(char, usize)> {
+    histogram(grid_characters(s))
+}
+
+fn histogram(characters: impl Iterator<Item = char>) -> Vec<(char, usize)> {
+    let mut histogram = characters.counts().into_iter().collect::<Vec<(char, usize)>>();
+    
+    histogram.sort_by_key(|&(c, count)| (Reverse(count), c));
+    histogram
 }
 
-fn radius(s: &str) -> usize {
+/// The fraction of the cells that must be background that hold the background character taken
+/// from the centre. A low value suggests the centre holds a stray character rather than the
+/// background. NaN if the input has no centre character.
+pub fn background_confidence(s: &str) -> f64 {
+    let h = height(s);
+    let Some(background) = background_character(s) else {
+        return f64::NAN;
+    };
+    
+    let histogram = histogram(cells(s).filter(|&(x, y, _)| required_background(x, y, h)).map(|(_, _, c)| c));
+    let total = histogram.iter().map(|&(_, count)| count).sum::<usize>();
+    let matching = histogram.iter().find(|&&(c, _)| c == background).map_or(0, |&(_, count)| count);
+    
+    matching as f64 / total as f64
+}
+
+/// The radius of the circle that fits a square input, which is half the side length rounded down.
+///
+/// ```
+/// use text_circle_validator::radius;
+///
+/// let circle = ".###.\n#...#\n#...#\n#...#\n.###.\n";
+/// assert_eq!(radius(circle), 2);
+/// ```
+pub fn radius(s: &str) -> usize {
     height(s) / 2
 }
 
-fn background_character(s: &str) -> char {
+/// The character at the centre of the input, or `None` if the input has no centre character.
+///
+/// ```
+/// use text_circle_validator::background_character;
+///
+/// let circle = ".###.\n#...#\n#...#\n#...#\n.###.\n";
+/// assert_eq!(background_character(circle), Some('.'));
+/// assert_eq!(background_character(""), None);
+/// ```
+pub fn background_character(s: &str) -> Option<char> {
     let r = radius(s);
     
-    character_at(r, r, s)
+    char_at(s, r, r)
 }
 
-fn character_at(x: usize, y: usize, s: &str) -> char {
-    let line = lines(s)[y];
+/// The number of contiguous foreground characters crossing the ring along the horizontal radius,
+/// counted from the left edge towards the centre. The cell next to the edge on this radius must
+/// be background, so this is always 1 for a valid circle and only exceeds 1 for invalid input.
+pub fn ring_thickness(s: &str) -> usize {
+    let r = radius(s);
+    let Some(background) = background_character(s) else {
+        return 0;
+    };
     
-    line.chars().collect::<Vec<char>>()[x]
+    (0..=r)
+        .filter_map(|x| char_at(s, x, r))
+        .skip_while(|&c| c == background)
+        .take_while(|&c| c != background)
+        .count()
 }
 
-fn missing_background_characters(s: &str) -> Vec<(usize, usize)> {
-    let background = background_character(s);
-    let mut missing_characters: Vec<(usize, usize)> = vec!();
-    let r = radius(s);
+/// The ring cells that have another ring cell directly inside them, in row-major order. Looking
+/// one step towards the centre, rounded to the nearest of the 8 directions, from each foreground
+/// cell that is allowed to be part of the ring finds where the ring is more than 1 cell thick.
+/// Input that is not square or has too small a radius has no ring to measure and passes.
+pub fn assert_thin_ring(s: &str) -> Result<(), Vec<Location>> {
+    let Ok(grid) = parse(s) else {
+        return Ok(());
+    };
+    
+    let r = grid.size / 2;
+    
+    if r < MIN_RADIUS {
+        return Ok(());
+    }
+    
+    let foreground = grid.foreground_mask(BackgroundStrategy::Centre.grid_background(&grid));
+    let on_ring = |l: &Location| foreground[l.y][l.x] && !required_background(l.x, l.y, grid.size);
+    
+    let thick = (0..grid.size)
+        .cartesian_product(0..grid.size)
+        .map(|(y, x)| Location::new(x, y))
+        .filter(|l| on_ring(l) && on_ring(&step_towards_centre(l, r)))
+        .collect::<Vec<Location>>();
+    
+    if thick.is_empty() { Ok(()) } else { Err(thick) }
+}
+
+// Never called for the centre itself, which has no direction towards the centre.
+fn step_towards_centre(l: &Location, r: usize) -> Location {
+    let (dx, dy) = (r as f64 - l.x as f64, r as f64 - l.y as f64);
+    let length = dx.hypot(dy);
+    
+    Location::new((l.x as f64 + (dx / length).round()) as usize, (l.y as f64 + (dy / length).round()) as usize)
+}
+
+/// The fewest foreground cells crossed by a straight line from the centre to the edge, taken
+/// in many directions. Each line steps only between cells that share a side, so 0 means the
+/// inside leaks out along that line. Only the first run of foreground out from the centre is
+/// counted. Input that is not square, or has too small a radius, gives 0.
+pub fn min_wall_thickness(s: &str) -> usize {
+    let Ok(grid) = parse(s) else {
+        return 0;
+    };
+    
+    let r = grid.size / 2;
+    
+    if r < MIN_RADIUS {
+        return 0;
+    }
+    
+    let background = BackgroundStrategy::Centre.grid_background(&grid);
+    // Enough directions for neighbouring lines to be less than a cell apart at the ring.
+    let directions = 16 * r;
+    
+    (0..directions)
+        .map(|i| {
+            let angle = i as f64 * std::f64::consts::TAU / directions as f64;
+            
+            ray_cells(r, angle, grid.size)
+                .into_iter()
+                .map(|l| grid.cells[l.y][l.x])
+                .skip_while(|&c| c == background)
+                .take_while(|&c| c != background)
+                .count()
+        })
+        .min()
+        .unwrap()
+}
+
+// The cells a line from the centre of the middle cell passes through on its way to the edge, in
+// order. This is a grid traversal, so consecutive cells always share a side.
+fn ray_cells(r: usize, angle: f64, size: usize) -> Vec<Location> {
+    let (dx, dy) = (angle.cos(), angle.sin());
+    let (step_x, step_y) = (if dx < 0.0 { -1 } else { 1 }, if dy < 0.0 { -1 } else { 1 });
+    let (delta_x, delta_y) = (1.0 / dx.abs(), 1.0 / dy.abs());
+    let (mut next_x, mut next_y) = (delta_x / 2.0, delta_y / 2.0);
+    let mut current = Some(Location::new(r, r));
+    let mut cells = vec![];
+    
+    while let Some(l) = current.filter(|l| l.x < size && l.y < size) {
+        cells.push(l);
+        
+        current = if next_x < next_y {
+            next_x += delta_x;
+            l.offset(step_x, 0)
+        } else {
+            next_y += delta_y;
+            l.offset(0, step_y)
+        };
+    }
+    
+    cells
+}
+
+/// The number of characters that are not the background character, or `None` if the background
+/// character cannot be determined.
+pub fn foreground_count(s: &str) -> Option<usize> {
+    let background = background_character(s)?;
+    
+    Some(cells(s).filter(|&(_, _, c)| c != background).count())
+}
+
+fn perimeter_character(s: &str) -> Option<char> {
+    perimeter_background(cells(s), height(s), CircleKind::Outline)
+}
+
+// A flush ring covers most of the border, so the border alone would often give the foreground.
+// The cells outside the ring must be background whatever the border holds. A square too small to
+// have any goes by its centre instead, which is background in an outline but part of the shape
+// in a filled disk.
+fn perimeter_background(cells: impl Iterator<Item = (usize, usize, char)>, h: usize, kind: CircleKind) -> Option<char> {
+    let cells = cells.collect::<Vec<(usize, usize, char)>>();
+    let outer = RingTolerance::default().outer;
+    let r = h / 2;
+    let centre = cells.iter().find(|&&(x, y, _)| x == r && y == r).map(|&(_, _, c)| c);
+    let most_common = |counted: &dyn Fn(usize, usize, char) -> bool| {
+        cells.iter()
+            .filter(|&&(x, y, c)| counted(x, y, c))
+            .map(|&(_, _, c)| c)
+            .counts()
+            .into_iter()
+            .max_by_key(|&(c, count)| (count, c))
+            .map(|(c, _)| c)
+    };
+    
+    most_common(&|x, y, _| outside_ring(x, y, h, outer, DistanceMetric::Euclidean)).or_else(|| match kind {
+        CircleKind::Outline => centre,
+        CircleKind::Filled => most_common(&|_, _, c| Some(c) != centre).or(centre),
+    })
+}
+
+/// Every character in the input with its position, as `(x, y, character)` in row-major order.
+pub fn cells(s: &str) -> impl Iterator<Item = (usize, usize, char)> + '_ {
+    s.lines().enumerate().flat_map(|(y, line)| line.chars().enumerate().map(move |(x, c)| (x, y, c)))
+}
+
+/// The byte offset into `s` of the character at column `x` of line `y`, or `None` if there is no
+/// such line or the line is too short. Multi-byte characters and `\r\n` line endings are
+/// accounted for.
+pub fn byte_offset(s: &str, x: usize, y: usize) -> Option<usize> {
+    let line_start = s.split_inclusive('\n').take(y).map(str::len).sum::<usize>();
+    let line = s[line_start..].lines().next()?;
+    
+    line.char_indices().nth(x).map(|(offset, _)| line_start + offset)
+}
+
+/// The character at column `x` of line `y`, or `None` if there is no such line or the line is
+/// too short.
+pub fn char_at(s: &str, x: usize, y: usize) -> Option<char> {
+    s.lines().nth(y)?.chars().nth(x)
+}
+
+fn missing_background_characters(foreground: &[Vec<bool>], outside_only: bool, tolerance: RingTolerance, metric: DistanceMetric) -> Vec<(usize, usize)> {
+    let mut missing_characters: Vec<(usize, usize)> = vec![];
+    let h = foreground.len();
     
-    for (y, line) in s.lines().enumerate() {
-        for (x, character) in line.chars().enumerate() {
-            if character != background && required_background(x, y, r) {
+    for (y, row) in foreground.iter().enumerate() {
+        for (x, &is_foreground) in row.iter().enumerate() {
+            let required = if outside_only {
+                outside_ring(x, y, h, tolerance.outer, metric)
+            } else {
+                required_background_with(x, y, h, tolerance.inner, tolerance.outer, metric)
+            };
+            
+            if is_foreground && required {
                 missing_characters.push((x, y))
             }
         }
@@ -121,76 +2618,200 @@ fn missing_background_characters(s: &str) -> Vec<(usize, usize)> {
     missing_characters
 }
 
-fn br_separated_tuples(v: &Vec<(usize, usize)>) -> String {
-    v.iter().map(|t| format!("({}, {})", t.0, t.1)).join("<br>")
+fn missing_foreground_characters(foreground: &[Vec<bool>], tolerance: RingTolerance, metric: DistanceMetric) -> Vec<(usize, usize)> {
+    let mut missing_characters: Vec<(usize, usize)> = vec![];
+    let h = foreground.len();
+    
+    for (y, row) in foreground.iter().enumerate() {
+        for (x, &is_foreground) in row.iter().enumerate() {
+            if !is_foreground && required_foreground(x, y, h, tolerance, metric) {
+                missing_characters.push((x, y))
+            }
+        }
+    }
+    
+    missing_characters
 }
 
-fn required_background(x: usize, y: usize, r: usize) -> bool {
-    let (x_offset, y_offset) = (x.abs_diff(r), y.abs_diff(r));
-    let distance = ((x_offset * x_offset + y_offset * y_offset) as f64).sqrt();
+fn missing_fill_characters(foreground: &[Vec<bool>], tolerance: RingTolerance, metric: DistanceMetric) -> Vec<(usize, usize)> {
+    let h = foreground.len();
     
-    distance <= (r - 1) as f64 || distance >= (r + 1) as f64
+    (0..h)
+        .cartesian_product(0..h)
+        .filter(|&(y, x)| !foreground[y][x] && inside_ring(x, y, h, tolerance.inner, metric))
+        .map(|(y, x)| (x, y))
+        .collect()
 }
 
-fn path_out_of_circle(s: &str) -> Option<String> {
-    let r = radius(s);
-    let centre = Location::new(r, r);
-    let start = PathStep::new(centre, None, 0);
-    let background = background_character(s);
+// The title sits in the top border, which is widened if the diagram is narrower than the title.
+fn boxed(rows: &[String], radius: usize) -> String {
+    let title = format!(" radius {radius} ");
+    let width = rows.iter().map(|row| row.chars().count()).chain([title.chars().count()]).max().unwrap();
+    let top = format!("┌{title}{}┐", "─".repeat(width - title.chars().count()));
+    let bottom = format!("└{}┘", "─".repeat(width));
+    let framed_rows = rows.iter().map(|row| format!("│{row}{}│", " ".repeat(width - row.chars().count())));
     
-    let mut unfound = vec!();
-    let h = height(s);
-        
-    for y in 0..h {
-        for x in 0..h {
-            if character_at(x, y, s) == background {
-                let l = Location::new(x, y);
-                
-                if l != centre {
-                    unfound.push(l)
-                }
-            }
+    [top].into_iter().chain(framed_rows).chain([bottom]).join("\n")
+}
+
+fn separated_tuples(v: &[(usize, usize)], order: CoordinateOrder, format: DiagramFormat, max_reported: Option<usize>) -> String {
+    let reported = max_reported.unwrap_or(v.len()).min(v.len());
+    let mut lines = v[..reported].iter().map(|&t| order.arrange(t)).map(|t| format!("({}, {})", t.0, t.1)).collect::<Vec<String>>();
+    
+    if reported < v.len() {
+        lines.push(format!("...and {} more", v.len() - reported));
+    }
+    
+    lines.join(format.line_break())
+}
+
+// The ring predicates take the side length of the square rather than the radius, so that the
+// centre of an even sided square can fall between cells.
+fn required_background(x: usize, y: usize, size: usize) -> bool {
+    let RingTolerance { inner, outer } = RingTolerance::default();
+    
+    required_background_with(x, y, size, inner, outer, DistanceMetric::Euclidean)
+}
+
+fn required_background_with(x: usize, y: usize, size: usize, inner_tolerance: f64, outer_tolerance: f64, metric: DistanceMetric) -> bool {
+    inside_ring(x, y, size, inner_tolerance, metric) || outside_ring(x, y, size, outer_tolerance, metric)
+}
+
+// Ring band cells that would join the inside of the circle directly to the outside if they
+// held the background character. Every valid outline must draw these cells.
+fn required_foreground(x: usize, y: usize, h: usize, tolerance: RingTolerance, metric: DistanceMetric) -> bool {
+    if required_background_with(x, y, h, tolerance.inner, tolerance.outer, metric) {
+        return false;
+    }
+    
+    let neighbours = neighbours(Location::new(x, y), Connectivity::Orthogonal)
+        .into_iter()
+        .filter(|n| n.x < h && n.y < h)
+        .collect::<Vec<Location>>();
+    
+    let touches_inside = neighbours.iter().any(|n| inside_ring(n.x, n.y, h, tolerance.inner, metric));
+    let touches_outside = edge_square(&Location::new(x, y), h) || neighbours.iter().any(|n| outside_ring(n.x, n.y, h, tolerance.outer, metric));
+    
+    touches_inside && touches_outside
+}
+
+fn inside_ring(x: usize, y: usize, size: usize, tolerance: f64, metric: DistanceMetric) -> bool {
+    distance_from_centre(x, y, size, metric) <= ring_radius(size) - tolerance
+}
+
+fn outside_ring(x: usize, y: usize, size: usize, tolerance: f64, metric: DistanceMetric) -> bool {
+    distance_from_centre(x, y, size, metric) >= ring_radius(size) + tolerance
+}
+
+// The distance from the centre of the square to the middle of each side cell, which is half a
+// cell less than half the side length.
+fn ring_radius(size: usize) -> f64 {
+    (size - 1) as f64 / 2.0
+}
+
+fn distance_from_centre(x: usize, y: usize, size: usize, metric: DistanceMetric) -> f64 {
+    let centre = ring_radius(size);
+    
+    metric.distance(x as f64 - centre, y as f64 - centre)
+}
+
+fn path_out_of_circle(foreground: &[Vec<bool>], connectivity: Connectivity, strategy: BackgroundStrategy, tolerance: RingTolerance, metric: DistanceMetric) -> Option<Vec<Location>> {
+    let h = foreground.len();
+    
+    match strategy {
+        BackgroundStrategy::Centre => {
+            let centre_background = centre_cells(h).into_iter().filter(|l| !foreground[l.y][l.x]).collect();
+            
+            background_path(foreground, connectivity, centre_background, |l| edge_square(l, h), |l| distance_to_edge(l, h))
+        }
+        BackgroundStrategy::Perimeter => {
+            let edge_background = (0..h).flat_map(|y| (0..h).map(move |x| Location::new(x, y)))
+                .filter(|l| edge_square(l, h) && !foreground[l.y][l.x])
+                .collect();
+            
+            background_path(foreground, connectivity, edge_background, |l| inside_ring(l.x, l.y, h, tolerance.inner, metric), |_| 0)
+                .map(|path| path.into_iter().rev().collect())
         }
     }
+}
+
+// The middle cell of an odd sided square, or the 2 by 2 block in the middle of an even sided one.
+fn centre_cells(size: usize) -> Vec<Location> {
+    let r = size / 2;
+    
+    if size % 2 == 1 {
+        vec![Location::new(r, r)]
+    } else {
+        vec![Location::new(r - 1, r - 1), Location::new(r, r - 1), Location::new(r - 1, r), Location::new(r, r)]
+    }
+}
+
+fn reachable_cells(foreground: &[Vec<bool>], connectivity: Connectivity, seeds: Vec<Location>) -> HashSet<Location> {
+    let mut reachable = HashSet::new();
+    // With no target the search visits every background cell reachable from the seeds.
+    traced_background_path(foreground, connectivity, seeds, |_| false, |_| 0, |step| {
+        reachable.insert(step.location);
+    });
+    
+    reachable
+}
+
+fn path_out_of_circle_from(seed: Location, foreground: &[Vec<bool>], connectivity: Connectivity) -> Option<Vec<Location>> {
+    let h = foreground.len();
+    
+    background_path(foreground, connectivity, vec![seed], |l| edge_square(l, h), |l| distance_to_edge(l, h))
+}
+
+// An A* search through background cells. The heuristic must never overestimate the number of
+// steps from a location to the nearest target.
+fn background_path(foreground: &[Vec<bool>], connectivity: Connectivity, starts: Vec<Location>, is_target: impl Fn(&Location) -> bool, heuristic: impl Fn(&Location) -> usize) -> Option<Vec<Location>> {
+    traced_background_path(foreground, connectivity, starts, is_target, heuristic, |_| {})
+}
+
+// The search, calling `visit` with each step in the order it is taken from the queue.
+fn traced_background_path(foreground: &[Vec<bool>], connectivity: Connectivity, starts: Vec<Location>, is_target: impl Fn(&Location) -> bool, heuristic: impl Fn(&Location) -> usize, mut visit: impl FnMut(&PathStep)) -> Option<Vec<Location>> {
+    // A grid of flags rather than a set of locations, since a sealed circle means visiting
+    // every background cell inside it and lookups dominate the search.
+    let mut unfound = foreground.iter().map(|row| row.iter().map(|&is_foreground| !is_foreground).collect()).collect::<Vec<Vec<bool>>>();
     
-    let mut found_to_check = vec!();
-    found_to_check.push(start);
+    for start in &starts {
+        unfound[start.y][start.x] = false;
+    }
     
-    let mut checked = vec!();
+    let mut found_to_check = starts.into_iter().map(|start| PathStep::new(start, None, 0, heuristic(&start))).collect::<BinaryHeap<PathStep>>();
+    let mut checked = HashMap::new();
     
     loop {
-        if found_to_check.len() == 0 {
-            return None;
-        }
-        
-        found_to_check.sort_by(|a, b| b.distance.cmp(&a.distance));
-        let candidate = found_to_check.pop().unwrap();
+        let candidate = found_to_check.pop()?;
+        visit(&candidate);
         
-        if edge_square(&candidate.location, h) {
-            return Some(path_diagram(&candidate, &checked, s, h));
+        if is_target(&candidate.location) {
+            return Some(path_locations(&candidate, &checked));
         }
         
-        let unfound_cloned = unfound.clone();
-        let unfound_neighbours = neighbours_in_unfound(&candidate, &unfound_cloned);
-        
-        for neighbour in unfound_neighbours {
-            unfound.swap_remove(unfound.iter().position(|u| u == neighbour).unwrap());      
-            found_to_check.push(PathStep::new(*neighbour, Some(candidate.location), candidate.distance + 1));
+        for neighbour in neighbours_in_unfound(&candidate, &unfound, connectivity) {
+            unfound[neighbour.y][neighbour.x] = false;
+            found_to_check.push(PathStep::new(neighbour, Some(candidate.location), candidate.distance + 1, heuristic(&neighbour)));
         }
         
-        checked.push(candidate);
+        checked.insert(candidate.location, candidate);
     }
 }
 
+fn distance_to_edge(l: &Location, height: usize) -> usize {
+    let Location { x, y } = *l;
+    
+    x.min(y).min(height - 1 - x).min(height - 1 - y)
+}
+
 fn edge_square(l: &Location, height: usize) -> bool {
     let Location { x, y } = l;
     
     *x == 0 || *y == 0 || *x == height - 1 || *y == height - 1
 }
 
-fn path_diagram(last_step: &PathStep, checked_squares: &Vec<PathStep>, s: &str, h: usize) -> String {
-    let paving = character_to_pave_with(s);
-    let mut path_squares = vec!();
+fn path_locations(last_step: &PathStep, checked_squares: &HashMap<Location, PathStep>) -> Vec<Location> {
+    let mut path_squares = vec![];
     
     let mut current_step = last_step;
     
@@ -199,36 +2820,61 @@ fn path_diagram(last_step: &PathStep, checked_squares: &Vec<PathStep>, s: &str,
         path_squares.push(current_location);
         
         current_step = match current_step.parent {
-            Some(parent_location) => checked_squares.iter().filter(|s| s.location == parent_location).collect::<Vec<&PathStep>>()[0],
+            Some(parent_location) => &checked_squares[&parent_location],
             None => break,
         }
     }
     
-    let mut diagram_rows = vec!();
+    path_squares.reverse();
+    path_squares
+}
+
+fn path_diagram(path_squares: &[Location], grid: &Grid, paving: Option<char>) -> Vec<String> {
+    let paving = paving.unwrap_or_else(|| character_to_pave_with(grid));
+    let path_squares = path_squares.iter().collect::<HashSet<&Location>>();
+    let mut diagram_rows = vec![];
     
-    for y in 0..h {
-        let mut row = "".to_string();
-        
-        for x in 0..h {
-            row = format!("{}{}", row, if path_squares.iter().any(|&s| s == Location{x, y}) { paving } else { character_at(x, y, s) });
-        }
+    for (y, line) in grid.cells.iter().enumerate() {
+        let row = line.iter().enumerate()
+            .map(|(x, &character)| if path_squares.contains(&Location{x, y}) { paving } else { character })
+            .collect::<String>();
         
         diagram_rows.push(row);
     }
     
-    diagram_rows.join("<br>")    
+    diagram_rows
 }
 
-fn neighbours_in_unfound<'a>(candidate: &PathStep, unfound: &'a Vec<Location>) -> Vec<&'a Location> {
-    let c = candidate.location;
-    
-    unfound.iter().filter(|l| l.manhattan_distance(&c) == 1).collect()
+/// The locations adjacent to `l` under the given connectivity, in order of y and then x. Cells
+/// in row or column 0 have no neighbours above or to the left. There is no upper bound, so the
+/// caller must discard any neighbours outside its grid.
+pub fn neighbours(l: Location, connectivity: Connectivity) -> Vec<Location> {
+    Location::DIRECTIONS
+        .into_iter()
+        .filter_map(|(dx, dy)| l.offset(dx, dy))
+        .filter(|n| connectivity.adjacent(n, &l))
+        .collect()
 }
 
-fn character_to_pave_with(s: &str) -> char {
-    let used_characters = distinct_characters(s);
-    let potential_paving = vec!['#', 'X', '.'];
-    
-    *(potential_paving.iter().filter(|&c| used_characters.iter().all(|u| u != c)).collect::<Vec<&char>>()[0])
+// Neighbours are returned in order of y and then x, so the search is deterministic.
+fn neighbours_in_unfound(candidate: &PathStep, unfound: &[Vec<bool>], connectivity: Connectivity) -> Vec<Location> {
+    neighbours(candidate.location, connectivity)
+        .into_iter()
+        .filter(|l| unfound.get(l.y).and_then(|row| row.get(l.x)).copied().unwrap_or(false))
+        .collect()
 }
 
+fn character_to_pave_with(grid: &Grid) -> char {
+    unused_characters(grid).next().unwrap()
+}
+
+// The input can only use finitely many characters, so searching on from the box drawing
+// characters is certain to find as many unused ones as are needed.
+fn unused_characters(grid: &Grid) -> impl Iterator<Item = char> {
+    let used_characters = grid.distinct_characters();
+    let potential_paving = ['#', 'X', '.', '*', '@', '+', 'O', '='];
+    
+    potential_paving.into_iter()
+        .chain('\u{2500}'..=char::MAX)
+        .filter(move |c| !used_characters.contains(c))
+}
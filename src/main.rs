@@ -0,0 +1,81 @@
+use std::io::Read;
+use std::process::ExitCode;
+use std::{env, fs, io};
+
+use text_circle_validator::{combining_characters, result_json, validate, CoordinateOrder, DiagramFormat, Indexing, ValidationResult};
+
+const USAGE: &str = "Usage: text_circle_validator [--json] [--diagram] [--box] [--one-based] [FILE]
+
+Validates the text circle in FILE, or in standard input if no FILE is given.
+Exits with 0 if the circle is valid and 1 if it is not.
+
+Options:
+    --json       Print the result as JSON
+    --diagram    Print the escape path diagram if there is one
+    --box        Frame the diagram in a box
+    --one-based  Count positions from (1, 1) instead of (0, 0)";
+
+fn main() -> ExitCode {
+    let mut json = false;
+    let mut diagram = false;
+    let mut boxed = false;
+    let mut indexing = Indexing::ZeroBased;
+    let mut path = None;
+    
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--diagram" => diagram = true,
+            "--box" => boxed = true,
+            "--one-based" => indexing = Indexing::OneBased,
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return ExitCode::SUCCESS;
+            }
+            _ if arg.starts_with('-') || path.is_some() => {
+                eprintln!("{USAGE}");
+                return ExitCode::from(2);
+            }
+            _ => path = Some(arg),
+        }
+    }
+    
+    let input = match path {
+        Some(path) => fs::read_to_string(&path).map_err(|e| format!("Could not read {path}: {e}")),
+        None => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input).map(|_| input).map_err(|e| format!("Could not read standard input: {e}"))
+        }
+    };
+    
+    let input = match input {
+        Ok(input) => input,
+        Err(message) => {
+            eprintln!("{message}");
+            return ExitCode::from(2);
+        }
+    };
+    
+    let result = validate(&input);
+    
+    for combining in combining_characters(&input) {
+        eprintln!("Warning: {combining}");
+    }
+    
+    if json {
+        let reported = if indexing == Indexing::OneBased { result.one_based() } else { result.clone() };
+        println!("{}", result_json(&reported));
+    } else if let ValidationResult::EscapePath { diagram: rows, radius, .. } = &result {
+        println!("Invalid. There should not be a path from inside the circle to outside.");
+        
+        if diagram {
+            println!();
+            let format = if boxed { DiagramFormat::BoxedText } else { DiagramFormat::PlainText };
+            println!("{}", format.diagram(rows, *radius));
+        }
+    } else {
+        println!("{}", result.describe(CoordinateOrder::default(), DiagramFormat::PlainText, None, indexing));
+    }
+    
+    if result.is_valid() { ExitCode::SUCCESS } else { ExitCode::FAILURE }
+}
@@ -0,0 +1,103 @@
+use std::fmt;
+
+use crate::{background_character, cells, validate, ValidationError};
+
+/// The reason a circle could not be drawn as an image.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RenderError {
+    /// The input is not a valid circle.
+    Invalid(ValidationError),
+    /// Each cell would be drawn 0 pixels wide, giving an empty image.
+    ZeroScale,
+}
+
+impl fmt::Display for RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RenderError::Invalid(error) => write!(f, "{error}"),
+            RenderError::ZeroScale => write!(f, "Each cell must be at least 1 pixel wide."),
+        }
+    }
+}
+
+impl std::error::Error for RenderError {}
+
+impl From<ValidationError> for RenderError {
+    fn from(error: ValidationError) -> Self {
+        RenderError::Invalid(error)
+    }
+}
+
+/// An SVG image of a valid circle, with each cell a `cell_px` by `cell_px` square. Foreground
+/// cells are drawn as black squares and background cells are left transparent. An invalid circle
+/// is reported as [`RenderError::Invalid`] before the cell size is checked, and a `cell_px` of 0
+/// is [`RenderError::ZeroScale`] rather than an empty image.
+pub fn render_svg(s: &str, cell_px: usize) -> Result<String, RenderError> {
+    svg(s, cell_px, false)
+}
+
+/// The image [`render_svg`] gives, with a grey line along every cell boundary, including the
+/// outer edge of the square. Errors are the same as for [`render_svg`].
+pub fn render_svg_with_grid_lines(s: &str, cell_px: usize) -> Result<String, RenderError> {
+    svg(s, cell_px, true)
+}
+
+fn svg(s: &str, cell_px: usize, grid_lines: bool) -> Result<String, RenderError> {
+    let r = validate(s).into_radius()?;
+    
+    if cell_px == 0 {
+        return Err(RenderError::ZeroScale);
+    }
+    
+    let background = background_character(s).unwrap();
+    let size = (2 * r + 1) * cell_px;
+    let mut elements = vec![];
+    
+    for (x, y, _) in cells(s).filter(|&(_, _, c)| c != background) {
+        let (left, top) = (x * cell_px, y * cell_px);
+        elements.push(format!(r#"<rect x="{left}" y="{top}" width="{cell_px}" height="{cell_px}"/>"#));
+    }
+    
+    if grid_lines {
+        for i in 0..=(2 * r + 1) {
+            let offset = i * cell_px;
+            elements.push(format!(r#"<line x1="{offset}" y1="0" x2="{offset}" y2="{size}" stroke="grey"/>"#));
+            elements.push(format!(r#"<line x1="0" y1="{offset}" x2="{size}" y2="{offset}" stroke="grey"/>"#));
+        }
+    }
+    
+    let header = format!(r#"<svg xmlns="http://www.w3.org/2000/svg" width="{size}" height="{size}" viewBox="0 0 {size} {size}">"#);
+    
+    Ok(format!("{header}\n{}\n</svg>", elements.join("\n")))
+}
+
+/// A PNG image of a valid circle, with each cell drawn as a `scale` by `scale` square of black
+/// for foreground or white for background. A scale of 0 is an error rather than an empty image.
+#[cfg(feature = "image")]
+pub fn render_png(s: &str, scale: usize) -> Result<Vec<u8>, RenderError> {
+    use image::{GrayImage, ImageFormat, Luma};
+    use std::io::Cursor;
+    
+    let r = validate(s).into_radius()?;
+    
+    if scale == 0 {
+        return Err(RenderError::ZeroScale);
+    }
+    
+    let background = background_character(s).unwrap();
+    let foreground = s.lines()
+        .map(|line| line.chars().map(|c| c != background).collect::<Vec<bool>>())
+        .collect::<Vec<Vec<bool>>>();
+    let size = ((2 * r + 1) * scale) as u32;
+    
+    let image = GrayImage::from_fn(size, size, |px, py| {
+        let (x, y) = (px as usize / scale, py as usize / scale);
+        
+        if foreground[y][x] { Luma([0]) } else { Luma([255]) }
+    });
+    
+    let mut bytes = vec![];
+    image.write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png).expect("writing to memory cannot fail");
+    
+    Ok(bytes)
+}
@@ -0,0 +1,122 @@
+use serde::{Serialize, Serializer};
+use wasm_bindgen::prelude::*;
+
+use crate::{validate, Dimensions, Location, ValidationResult};
+
+impl Serialize for Location {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        [self.x, self.y].serialize(serializer)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Report {
+    pub valid: bool,
+    pub reason: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub radius: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dimensions: Option<Dimensions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jagged_rows: Option<Vec<(usize, usize)>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub character_count: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub character: Option<char>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position_count: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub positions: Option<Vec<(usize, usize)>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<Vec<Location>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exit: Option<Location>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_direction: Option<&'static str>,
+}
+
+impl From<&ValidationResult> for Report {
+    fn from(result: &ValidationResult) -> Self {
+        let mut report = Report {
+            valid: result.is_valid(),
+            reason: result.reason(),
+            radius: None,
+            dimensions: None,
+            jagged_rows: None,
+            character_count: None,
+            character: None,
+            position_count: None,
+            positions: None,
+            path: None,
+            exit: None,
+            first_direction: result.first_escape_direction(),
+        };
+        
+        match result {
+            ValidationResult::Valid { radius, .. } | ValidationResult::RadiusTooSmall(radius) => report.radius = Some(*radius),
+            ValidationResult::NotSquare { dimensions, jagged_rows } => {
+                report.dimensions = Some(*dimensions);
+                report.jagged_rows = Some(jagged_rows.clone());
+            }
+            ValidationResult::TooFewCharacters(count) | ValidationResult::TooManyCharacters(count) => report.character_count = Some(*count),
+            ValidationResult::UnexpectedCharacter { character, position } => {
+                report.character = Some(*character);
+                report.position_count = Some(1);
+                report.positions = Some(vec![*position]);
+            }
+            ValidationResult::MissingBackground { background: character, positions }
+            | ValidationResult::MissingForeground { foreground: character, positions } => {
+                report.character = Some(*character);
+                report.position_count = Some(positions.len());
+                report.positions = Some(positions.clone());
+            }
+            ValidationResult::EscapePath { path, exit, .. } => {
+                report.path = Some(path.clone());
+                report.exit = Some(*exit);
+            }
+            ValidationResult::Empty | ValidationResult::NotOdd | ValidationResult::CentreNotBackground => {}
+        }
+        
+        report
+    }
+}
+
+pub fn validate_json(s: &str) -> String {
+    result_json(&validate(s))
+}
+
+/// A result as JSON, in the same form as [`validate_json`], for a result that has been changed
+/// after validation, such as by [`ValidationResult::one_based`].
+pub fn result_json(result: &ValidationResult) -> String {
+    serde_json::to_string(&Report::from(result)).unwrap()
+}
+
+// The fields are always present, so the JavaScript side can rely on the shape of the object.
+#[derive(Serialize)]
+struct StructuredResult {
+    valid: bool,
+    reason: &'static str,
+    positions: Vec<(usize, usize)>,
+    diagram: Vec<String>,
+}
+
+impl From<&ValidationResult> for StructuredResult {
+    fn from(result: &ValidationResult) -> Self {
+        let (positions, diagram) = match result {
+            ValidationResult::MissingBackground { positions, .. }
+            | ValidationResult::MissingForeground { positions, .. } => (positions.clone(), vec![]),
+            ValidationResult::UnexpectedCharacter { position, .. } => (vec![*position], vec![]),
+            ValidationResult::EscapePath { path, diagram, .. } => (path.iter().map(|l| (l.x, l.y)).collect(), diagram.clone()),
+            _ => (vec![], vec![]),
+        };
+        
+        StructuredResult { valid: result.is_valid(), reason: result.reason(), positions, diagram }
+    }
+}
+
+/// Validates a text circle, returning an object with `valid`, `reason`, `positions` and
+/// `diagram` fields instead of an HTML string.
+#[wasm_bindgen]
+pub fn validate_structured(s: &str) -> JsValue {
+    serde_wasm_bindgen::to_value(&StructuredResult::from(&validate(s))).unwrap()
+}
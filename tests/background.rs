@@ -0,0 +1,98 @@
+mod common;
+
+use common::{edit, filled_disk, THIN_RING};
+use text_circle_validator::*;
+
+fn perimeter() -> ValidatorOptions {
+    ValidatorOptions { background: BackgroundStrategy::Perimeter, ..Default::default() }
+}
+
+#[test]
+fn perimeter_finds_the_background_of_flush_outlines() {
+    for radius in MIN_RADIUS..=12 {
+        let circle = generate_circle(Radius(radius), '#', '.').unwrap();
+        
+        assert_eq!(validate_with(&circle, &perimeter()), ValidationResult::Valid { radius, foreground: '#', background: '.' }, "radius {radius}");
+    }
+}
+
+#[test]
+fn perimeter_finds_the_background_of_a_filled_disk() {
+    let disk = filled_disk(10);
+    let options = ValidatorOptions { kind: CircleKind::Filled, ..perimeter() };
+    
+    assert_eq!(validate_with(&disk, &options), ValidationResult::Valid { radius: 10, foreground: '#', background: '.' });
+}
+
+#[test]
+fn perimeter_searches_from_the_edge() {
+    let leaking = edit(THIN_RING, 7, 1, '.');
+    
+    assert_eq!(validate_with(&leaking, &perimeter()).reason(), "escape_path");
+}
+
+#[test]
+fn filled_disks_are_valid_at_every_radius() {
+    let filled = ValidatorOptions { kind: CircleKind::Filled, ..Default::default() };
+    
+    for radius in MIN_RADIUS..=12 {
+        assert_eq!(validate_with(&filled_disk(radius), &filled), ValidationResult::Valid { radius, foreground: '#', background: '.' }, "radius {radius}");
+    }
+}
+
+#[test]
+fn a_filled_disk_is_not_a_valid_outline() {
+    assert!(!validate(&filled_disk(6)).is_valid());
+    assert!(!validate_with(&filled_disk(6), &perimeter()).is_valid());
+}
+
+#[test]
+fn an_outline_is_not_a_valid_filled_disk() {
+    let filled = ValidatorOptions { kind: CircleKind::Filled, ..Default::default() };
+    let outline = generate_circle(Radius(6), '#', '.').unwrap();
+    
+    assert_eq!(validate_with(&outline, &filled).reason(), "missing_foreground");
+}
+
+#[test]
+fn a_foreground_centre_is_reported_when_a_background_centre_is_required() {
+    let circle = generate_circle(Radius(10), '#', '.').unwrap();
+    let dotted = edit(&circle, 10, 10, '#');
+    let required = ValidatorOptions { require_background_centre: true, ..perimeter() };
+    
+    assert_eq!(validate_with(&dotted, &required), ValidationResult::CentreNotBackground);
+    assert!(validate_with(&circle, &required).is_valid());
+}
+
+#[test]
+fn different_backgrounds_inside_and_outside_need_a_split_background() {
+    let two_tone = THIN_RING.lines()
+        .map(|row| {
+            let (first, last) = (row.find('#').unwrap(), row.rfind('#').unwrap());
+            row.char_indices().map(|(x, c)| if c == '.' && x > first && x < last { ':' } else { c }).collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+    let split = ValidatorOptions { split_background: Some(BackgroundPair { inner: ':', outer: '.' }), ..Default::default() };
+    
+    assert_eq!(validate(&two_tone), ValidationResult::TooManyCharacters(3));
+    assert_eq!(validate_with(&two_tone, &split), ValidationResult::Valid { radius: 4, foreground: '#', background: ':' });
+}
+
+#[test]
+fn a_hole_in_a_filled_disk_is_missing_foreground() {
+    let filled = ValidatorOptions { kind: CircleKind::Filled, ..Default::default() };
+    
+    for (x, y) in [(8, 11), (10, 4), (5, 10)] {
+        assert_eq!(validate_with(&edit(&filled_disk(10), x, y, '.'), &filled), ValidationResult::MissingForeground { foreground: '#', positions: vec![(x, y)] });
+    }
+}
+
+#[test]
+fn an_outlier_at_the_centre_gives_low_confidence() {
+    let outlier = edit(THIN_RING, 4, 4, 'x');
+    
+    assert_eq!(background_confidence(THIN_RING), 1.0);
+    assert!(background_confidence(&outlier) < 0.1, "{}", background_confidence(&outlier));
+    assert!(background_confidence("").is_nan());
+}
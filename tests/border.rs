@@ -0,0 +1,40 @@
+mod common;
+
+use common::{edit, with_border, THIN_RING};
+use text_circle_validator::*;
+
+fn margin() -> ValidatorOptions {
+    ValidatorOptions { border: BorderPolicy::Margin, ..Default::default() }
+}
+
+#[test]
+fn a_flush_circle_is_valid_by_default() {
+    assert_eq!(ValidatorOptions::default().border, BorderPolicy::Flush);
+    assert_eq!(validate_with(THIN_RING, &ValidatorOptions::default()), ValidationResult::Valid { radius: 4, foreground: '#', background: '.' });
+}
+
+#[test]
+fn a_flush_circle_breaks_the_margin() {
+    let result = validate_with(THIN_RING, &margin());
+    
+    assert!(matches!(result, ValidationResult::MissingBackground { .. }), "{result:?}");
+}
+
+#[test]
+fn a_circle_inside_a_margin_needs_the_margin_policy() {
+    let padded = with_border(THIN_RING, '.');
+    
+    assert_eq!(validate_with(&padded, &margin()), ValidationResult::Valid { radius: 4, foreground: '#', background: '.' });
+    assert!(!validate(&padded).is_valid());
+}
+
+#[test]
+fn a_framed_circle_validates_inside_its_frame() {
+    let framed = with_border(THIN_RING, '+');
+    let frame = ValidatorOptions { border: BorderPolicy::Frame('+'), ..Default::default() };
+    let stray = edit(&framed, 4, 4, '#');
+    
+    assert_eq!(validate(&framed), ValidationResult::TooManyCharacters(3));
+    assert_eq!(validate_with(&framed, &frame), ValidationResult::Valid { radius: 4, foreground: '#', background: '.' });
+    assert_eq!(validate_with(&stray, &frame), ValidationResult::MissingBackground { background: '.', positions: vec![(4, 4)] });
+}
@@ -0,0 +1,31 @@
+use text_circle_validator::*;
+
+#[test]
+fn char_at_is_none_outside_the_input() {
+    let jagged = "abc\nd\nefg";
+    
+    assert_eq!(char_at(jagged, 2, 0), Some('c'));
+    assert_eq!(char_at(jagged, 3, 0), None);
+    assert_eq!(char_at(jagged, 0, 3), None);
+    assert_eq!(char_at(jagged, 1, 1), None);
+    assert_eq!(char_at(jagged, 0, 1), Some('d'));
+}
+
+#[test]
+fn cells_are_listed_in_row_major_order() {
+    let listed = cells("abc\ndef\nghi").collect::<Vec<(usize, usize, char)>>();
+    
+    assert_eq!(listed.len(), 9);
+    assert_eq!(listed[..4], [(0, 0, 'a'), (1, 0, 'b'), (2, 0, 'c'), (0, 1, 'd')]);
+    assert_eq!(listed[8], (2, 2, 'i'));
+}
+
+#[test]
+fn the_middle_row_of_a_ring_has_two_spans() {
+    let spans = row_spans(&generate_circle(Radius(4), '#', '.').unwrap());
+    
+    assert_eq!(spans.len(), 9);
+    assert_eq!(spans[4], vec![(0, 0), (8, 8)]);
+    assert_eq!(spans[0], vec![(2, 6)]);
+    assert!(row_spans("").is_empty());
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use common::{edit, THIN_RING};
+use text_circle_validator::*;
+
+#[test]
+fn two_foreground_characters_form_a_ring_when_allowed() {
+    let anti_aliased = edit(&edit(THIN_RING, 1, 1, '+'), 7, 7, '+');
+    let options = ValidatorOptions { allow_extra_foreground: true, ..Default::default() };
+    
+    assert_eq!(validate(&anti_aliased), ValidationResult::TooManyCharacters(3));
+    assert_eq!(validate_with(&anti_aliased, &options), ValidationResult::Valid { radius: 4, foreground: '#', background: '.' });
+}
+
+#[test]
+fn foreground_count_counts_the_ink() {
+    assert_eq!(foreground_count(&generate_circle(Radius(3), '#', '.').unwrap()), Some(32));
+    assert_eq!(foreground_count(THIN_RING), Some(24));
+    assert_eq!(foreground_count(""), None);
+}
+
+#[test]
+fn upper_and_lower_case_form_one_ring_when_case_is_ignored() {
+    let mixed = THIN_RING.replace('#', "O").replacen('O', "o", 4);
+    let options = ValidatorOptions { case_insensitive: true, ..Default::default() };
+    
+    assert_eq!(validate(&mixed), ValidationResult::TooManyCharacters(3));
+    assert_eq!(validate_with(&mixed, &options), ValidationResult::Valid { radius: 4, foreground: 'o', background: '.' });
+}
+
+#[test]
+fn the_histogram_lists_the_most_common_character_first() {
+    assert_eq!(character_histogram(THIN_RING), vec![('.', 57), ('#', 24)]);
+    assert_eq!(character_histogram("ab\nba\n"), vec![('a', 2), ('b', 2)]);
+}
+
+#[test]
+fn strict_mode_finds_a_tab_among_spaces() {
+    let circle = generate_circle(Radius(4), 'O', ' ').unwrap();
+    let tabbed = edit(&circle, 3, 5, '\t');
+    let options = ValidatorOptions { strict: true, ..Default::default() };
+    
+    assert_eq!(validate(&tabbed), ValidationResult::TooManyCharacters(3));
+    assert_eq!(validate_with(&tabbed, &options), ValidationResult::UnexpectedCharacter { character: '\t', position: (3, 5) });
+    assert!(validate_with(&circle, &options).is_valid());
+}
+
+#[test]
+fn crlf_line_endings_are_not_counted_as_characters() {
+    let crlf = format!("{}\r\n", THIN_RING.replace('\n', "\r\n"));
+    
+    assert_eq!(character_histogram(&crlf).len(), 2);
+    assert!(looks_like_circle(&crlf));
+    assert!(validate(&crlf).is_valid());
+}
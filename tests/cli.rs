@@ -0,0 +1,84 @@
+#![cfg(feature = "cli")]
+
+mod common;
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+use common::{edit, THIN_RING};
+use text_circle_validator::{generate_circle, Radius};
+
+fn run(args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_text_circle_validator"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+    
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn an_invalid_circle_exits_with_1() {
+    let output = run(&[], &edit(THIN_RING, 7, 1, '.'));
+    
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8(output.stdout).unwrap().starts_with("Invalid."));
+}
+
+#[test]
+fn a_valid_circle_exits_with_0() {
+    let output = run(&[], THIN_RING);
+    
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn a_file_argument_is_read_instead_of_standard_input() {
+    let path = std::env::temp_dir().join(format!("text_circle_validator_cli_{}.txt", std::process::id()));
+    std::fs::write(&path, edit(THIN_RING, 7, 1, '.')).unwrap();
+    let output = run(&[path.to_str().unwrap()], "");
+    std::fs::remove_file(&path).unwrap();
+    
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn the_diagram_is_printed_on_request() {
+    let gapped = edit(THIN_RING, 7, 1, '.');
+    let plain = String::from_utf8(run(&[], &gapped).stdout).unwrap();
+    let output = String::from_utf8(run(&["--diagram"], &gapped).stdout).unwrap();
+    
+    assert!(output.len() > plain.len() + THIN_RING.len(), "{output}");
+}
+
+#[test]
+fn json_is_printed_on_request() {
+    let output = run(&["--json"], &edit(THIN_RING, 7, 1, '.'));
+    
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8(output.stdout).unwrap().trim_start().starts_with('{'));
+}
+
+#[test]
+fn an_unknown_flag_exits_with_2() {
+    assert_eq!(run(&["--unknown"], "").status.code(), Some(2));
+}
+
+#[test]
+fn one_based_json_positions_are_shifted() {
+    let circle = edit(&generate_circle(Radius(6), '#', '.').unwrap(), 0, 0, '#');
+    let output = String::from_utf8(run(&["--json", "--one-based"], &circle).stdout).unwrap();
+    
+    assert!(output.contains(r#""positions":[[1,1]]"#), "{output}");
+}
+
+#[test]
+fn one_based_jagged_rows_are_shifted() {
+    let output = String::from_utf8(run(&["--one-based"], "...\n..\n...").stdout).unwrap();
+    
+    assert!(output.trim_end().ends_with("(2, 2)"), "{output}");
+}
@@ -0,0 +1,36 @@
+mod common;
+
+use common::edit;
+use text_circle_validator::*;
+
+fn hash_comments() -> ValidatorOptions {
+    ValidatorOptions { comment_prefix: Some('#'), ..Default::default() }
+}
+
+#[test]
+fn comment_lines_before_the_circle_are_ignored() {
+    for radius in 2..=8 {
+        let circle = generate_circle(Radius(radius), '#', '.').unwrap();
+        let commented = format!("# title\n# radius {radius}\n{circle}");
+        
+        assert_eq!(validate(&commented).reason(), "not_square");
+        assert_eq!(validate_with(&commented, &hash_comments()), validate(&circle), "radius {radius}");
+    }
+}
+
+#[test]
+fn rows_of_the_circle_are_kept_even_if_they_start_with_the_prefix() {
+    let circle = generate_circle(Radius(2), '#', '.').unwrap();
+    
+    assert!(circle.lines().all(|row| row.starts_with('#')));
+    assert_eq!(validate_with(&circle, &hash_comments()), validate(&circle));
+    assert_eq!(validate_with(&format!("# title\n{circle}"), &hash_comments()), validate(&circle));
+}
+
+#[test]
+fn positions_are_counted_without_the_comment_lines() {
+    let circle = edit(&generate_circle(Radius(6), '#', '.').unwrap(), 6, 5, '#');
+    let commented = format!("# title\n{circle}");
+    
+    assert_eq!(validate_with(&commented, &hash_comments()), ValidationResult::MissingBackground { background: '.', positions: vec![(6, 5)] });
+}
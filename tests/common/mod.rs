@@ -0,0 +1,38 @@
+#![allow(dead_code)]
+
+/// A circle of `#` on `.` with every cell inside the ring filled in.
+pub fn filled_disk(radius: usize) -> String {
+    let side = 2 * radius + 1;
+    
+    (0..side)
+        .map(|y| (0..side).map(|x| {
+            let (dx, dy) = (x.abs_diff(radius), y.abs_diff(radius));
+            if ((dx * dx + dy * dy) as f64).sqrt() < radius as f64 + 0.5 { '#' } else { '.' }
+        }).collect::<String>())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// The input with the character at column `x` of row `y` replaced.
+pub fn edit(s: &str, x: usize, y: usize, c: char) -> String {
+    let mut rows = s.lines().map(|row| row.chars().collect()).collect::<Vec<Vec<char>>>();
+    rows[y][x] = c;
+    
+    rows.iter().map(|row| row.iter().collect::<String>()).collect::<Vec<String>>().join("\n")
+}
+
+/// The input surrounded by a border one cell wide of the given character.
+pub fn with_border(s: &str, c: char) -> String {
+    let side = s.lines().count() + 2;
+    let edge = c.to_string().repeat(side);
+    
+    [edge.clone()].into_iter()
+        .chain(s.lines().map(|row| format!("{c}{row}{c}")))
+        .chain([edge])
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// A valid radius 4 outline drawn one cell thick, with every ring cell required to be
+/// foreground or to seal a diagonal step.
+pub const THIN_RING: &str = "..#####..\n.#.....#.\n#.......#\n#.......#\n#.......#\n#.......#\n#.......#\n.#.....#.\n..#####..";
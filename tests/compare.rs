@@ -0,0 +1,19 @@
+mod common;
+
+use common::{edit, THIN_RING};
+use text_circle_validator::*;
+
+#[test]
+fn a_broken_circle_compared_with_its_fix() {
+    let broken = edit(&edit(THIN_RING, 7, 1, '.'), 3, 3, '#');
+    let report = compare(&broken, THIN_RING).unwrap();
+    
+    assert_eq!(report, DiffReport { added_foreground: vec![(7, 1)], removed_foreground: vec![(3, 3)], changed_character: vec![] });
+    assert!(compare(THIN_RING, THIN_RING).unwrap().is_unchanged());
+}
+
+#[test]
+fn only_squares_of_the_same_size_can_be_compared() {
+    assert_eq!(compare(THIN_RING, &generate_circle(Radius(3), '#', '.').unwrap()), Err(CompareError::DifferentSizes(9, 7)));
+    assert_eq!(compare(THIN_RING, "ab"), Err(CompareError::NotSquare));
+}
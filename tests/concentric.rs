@@ -0,0 +1,20 @@
+mod common;
+
+use common::edit;
+use text_circle_validator::*;
+
+// A radius 8 ring with a radius 4 ring inside it.
+fn target() -> String {
+    let outer = generate_circle(Radius(8), '#', '.').unwrap();
+    let inner = generate_circle(Radius(4), '#', '.').unwrap();
+    
+    cells(&inner).filter(|&(_, _, c)| c == '#').fold(outer, |target, (x, y, _)| edit(&target, x + 4, y + 4, '#'))
+}
+
+#[test]
+fn a_two_ring_target_is_valid_when_concentric_rings_are_allowed() {
+    let options = ValidatorOptions { allow_concentric: true, ..Default::default() };
+    
+    assert!(matches!(validate(&target()), ValidationResult::MissingBackground { .. }));
+    assert_eq!(validate_with(&target(), &options), ValidationResult::Valid { radius: 8, foreground: '#', background: '.' });
+}
@@ -0,0 +1,29 @@
+mod common;
+
+use common::THIN_RING;
+use text_circle_validator::*;
+
+fn diagonal() -> ValidatorOptions {
+    ValidatorOptions { connectivity: Connectivity::Diagonal, ..Default::default() }
+}
+
+#[test]
+fn orthogonal_is_the_default() {
+    assert_eq!(ValidatorOptions::default().connectivity, Connectivity::Orthogonal);
+}
+
+#[test]
+fn a_thin_ring_is_sealed_orthogonally_but_leaks_diagonally() {
+    assert!(validate(THIN_RING).is_valid());
+    assert!(validate_with(THIN_RING, &ValidatorOptions::default()).is_valid());
+    
+    let result = validate_with(THIN_RING, &diagonal());
+    assert!(matches!(result, ValidationResult::EscapePath { .. }), "{result}");
+}
+
+#[test]
+fn generated_circles_are_sealed_diagonally() {
+    for radius in 2..=10 {
+        assert!(validate_with(&generate_circle(Radius(radius), '#', '.').unwrap(), &diagonal()).is_valid(), "radius {radius}");
+    }
+}
@@ -0,0 +1,14 @@
+mod common;
+
+use common::{with_border, THIN_RING};
+use text_circle_validator::*;
+
+#[test]
+fn cropping_a_padded_circle_leaves_the_circle() {
+    let padded = with_border(&with_border(THIN_RING, '.'), '.');
+    let cropped = crop_to_circle(&padded);
+    
+    assert!(!validate(&padded).is_valid());
+    assert_eq!(cropped, THIN_RING);
+    assert_eq!(validate(&cropped), ValidationResult::Valid { radius: 4, foreground: '#', background: '.' });
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use common::edit;
+use text_circle_validator::*;
+
+fn missing_at_2_5() -> ValidationResult {
+    ValidationResult::MissingBackground { background: '.', positions: vec![(2, 5)] }
+}
+
+#[test]
+fn positions_are_written_in_the_chosen_order() {
+    let xy = missing_at_2_5().describe(CoordinateOrder::XY, DiagramFormat::PlainText, None, Indexing::ZeroBased);
+    let row_col = missing_at_2_5().describe(CoordinateOrder::RowCol, DiagramFormat::PlainText, None, Indexing::ZeroBased);
+    
+    assert!(xy.contains("(x, y)") && xy.ends_with("(2, 5)"), "{xy}");
+    assert!(row_col.contains("(row, column)") && row_col.ends_with("(5, 2)"), "{row_col}");
+}
+
+#[test]
+fn a_plain_text_diagram_has_no_markup() {
+    let circle = generate_circle(Radius(3), '#', '.').unwrap();
+    let leaking = edit(&edit(&circle, 0, 2, '.'), 1, 2, '.');
+    let description = validate(&leaking).describe(CoordinateOrder::XY, DiagramFormat::PlainText, None, Indexing::ZeroBased);
+    
+    assert!(description.contains('\n'), "{description}");
+    assert!(!description.contains("<br>") && !description.contains("<code>"), "{description}");
+    assert!(validate(&leaking).to_string().contains("<br>"));
+}
+
+#[test]
+fn long_lists_of_positions_are_cut_short() {
+    let circle = generate_circle(Radius(20), '#', '.').unwrap();
+    let blotted = (10..20).flat_map(|y| (10..20).map(move |x| (x, y))).fold(circle, |blotted, (x, y)| edit(&blotted, x, y, '#'));
+    let result = validate(&blotted);
+    let description = result.describe(CoordinateOrder::XY, DiagramFormat::PlainText, Some(5), Indexing::ZeroBased);
+    
+    assert_eq!(result.position_count(), Some(100));
+    assert_eq!(description.lines().skip(1).collect::<Vec<&str>>(), ["(10, 10)", "(11, 10)", "(12, 10)", "(13, 10)", "(14, 10)", "...and 95 more"]);
+}
+
+#[test]
+fn a_valid_result_carries_its_radius() {
+    let circle = generate_circle(Radius(7), '#', '.').unwrap();
+    let ValidationResult::Valid { radius, foreground, background } = validate(&circle) else {
+        panic!("expected a valid circle");
+    };
+    
+    assert_eq!((radius, foreground, background), (7, '#', '.'));
+    assert_eq!(validate(&circle).to_string(), "This is a valid text circle of radius 7.");
+}
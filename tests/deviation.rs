@@ -0,0 +1,25 @@
+mod common;
+
+use common::{edit, THIN_RING};
+use text_circle_validator::*;
+
+#[test]
+fn a_stray_foreground_cell_is_marked() {
+    let diagram = deviation_diagram(&edit(THIN_RING, 3, 3, '#'), DiagramFormat::PlainText).unwrap();
+    let rows = diagram.lines().skip(2).collect::<Vec<&str>>();
+    
+    assert!(diagram.starts_with("Cells marked X should be background and cells marked * should be foreground:"), "{diagram}");
+    assert_eq!(rows.len(), 9);
+    assert_eq!(rows[3].chars().nth(3), Some('X'));
+    assert_eq!(rows.iter().map(|row| row.matches('X').count()).sum::<usize>(), 1);
+}
+
+#[test]
+fn deviation_cells_are_listed_as_x_y_and_kind() {
+    let cells = deviation_cells(&edit(THIN_RING, 3, 3, '#'));
+    
+    assert_eq!(cells.len() % 3, 0);
+    assert!(cells.chunks(3).any(|cell| cell == [3, 3, 0]));
+    assert!(cells.chunks(3).all(|cell| cell[2] <= 1));
+    assert!(deviation_cells("ab\ncd").is_empty());
+}
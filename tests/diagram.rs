@@ -0,0 +1,36 @@
+mod common;
+
+use common::{edit, with_border, THIN_RING};
+use text_circle_validator::*;
+
+fn boxed_diagram(result: &ValidationResult) -> Vec<String> {
+    let ValidationResult::EscapePath { diagram, radius, .. } = result else {
+        panic!("expected an escape path, got {result:?}");
+    };
+    
+    DiagramFormat::BoxedText.diagram(diagram, *radius).lines().map(String::from).collect()
+}
+
+#[test]
+fn a_boxed_diagram_is_framed_and_titled() {
+    let result = validate(&edit(THIN_RING, 7, 1, '.'));
+    let ValidationResult::EscapePath { diagram, .. } = &result else {
+        panic!("expected an escape path, got {result:?}");
+    };
+    let boxed = boxed_diagram(&result);
+    
+    assert_eq!(boxed.len(), diagram.len() + 2);
+    assert!(boxed[0].starts_with('┌') && boxed[0].ends_with('┐'));
+    assert!(boxed[0].contains(" radius 4 "));
+    assert!(boxed.last().unwrap().starts_with('└') && boxed.last().unwrap().ends_with('┘'));
+}
+
+#[test]
+fn the_title_gives_the_radius_of_a_circle_inside_a_margin_or_frame() {
+    let leaking = edit(THIN_RING, 7, 1, '.');
+    let margin = ValidatorOptions { border: BorderPolicy::Margin, ..Default::default() };
+    let frame = ValidatorOptions { border: BorderPolicy::Frame('+'), ..Default::default() };
+    
+    assert!(boxed_diagram(&validate_with(&with_border(&leaking, '.'), &margin))[0].contains(" radius 4 "));
+    assert!(boxed_diagram(&validate_with(&with_border(&leaking, '+'), &frame))[0].contains(" radius 4 "));
+}
@@ -0,0 +1,116 @@
+mod common;
+
+use common::{edit, THIN_RING};
+use text_circle_validator::*;
+
+// A radius 15 circle with a gap two cells wide in the left wall.
+fn gapped_circle() -> String {
+    let circle = generate_circle(Radius(15), '#', '.').unwrap();
+    
+    edit(&edit(&circle, 0, 12, '.'), 1, 12, '.')
+}
+
+const GAPPED_DIAGRAM: [&str; 31] = [
+    "..........###########..........",
+    "........#######.#######........",
+    "......####...........####......",
+    ".....###...............###.....",
+    "....###.................###....",
+    "...###...................###...",
+    "..###.....................###..",
+    "..##.......................##..",
+    ".##.........................##.",
+    ".##.........................##.",
+    "##...........................##",
+    "##...........................##",
+    "XXX..........................##",
+    "##X..........................##",
+    "##X..........................##",
+    "#.XXXXXXXXXXXXXX..............#",
+    "##...........................##",
+    "##...........................##",
+    "##...........................##",
+    "##...........................##",
+    "##...........................##",
+    ".##.........................##.",
+    ".##.........................##.",
+    "..##.......................##..",
+    "..###.....................###..",
+    "...###...................###...",
+    "....###.................###....",
+    ".....###...............###.....",
+    "......####...........####......",
+    "........#######.#######........",
+    "..........###########..........",
+];
+
+#[test]
+fn the_diagram_of_a_radius_15_gap_is_unchanged() {
+    let ValidationResult::EscapePath { diagram, .. } = validate(&gapped_circle()) else {
+        panic!("expected an escape path");
+    };
+    
+    assert_eq!(diagram, GAPPED_DIAGRAM);
+}
+
+#[test]
+fn the_path_is_a_contiguous_background_path() {
+    let gapped = gapped_circle();
+    let ValidationResult::EscapePath { path, .. } = validate(&gapped) else {
+        panic!("expected an escape path");
+    };
+    
+    for step in path.windows(2) {
+        assert_eq!(step[0].x().abs_diff(step[1].x()) + step[0].y().abs_diff(step[1].y()), 1, "{step:?}");
+    }
+    
+    assert!(path.iter().all(|l| gapped.lines().nth(l.y()).unwrap().chars().nth(l.x()) == Some('.')));
+}
+
+#[test]
+fn the_path_runs_from_the_centre_to_the_edge() {
+    for (gapped, radius) in [(gapped_circle(), 15), (edit(THIN_RING, 7, 1, '.'), 4)] {
+        let path = validate(&gapped).escape_path().unwrap().to_vec();
+        let last = path.last().unwrap();
+        
+        assert_eq!(path.first(), Some(&Location::new(radius, radius)));
+        assert!([last.x(), last.y()].iter().any(|&n| n == 0 || n == 2 * radius), "{last:?}");
+    }
+}
+
+#[test]
+fn the_shortest_path_is_found_the_same_way_every_time() {
+    let first = validate(&gapped_circle());
+    let second = validate(&gapped_circle());
+    
+    assert_eq!(first.to_string(), second.to_string());
+    assert_eq!(first.escape_path().unwrap().len(), 15 + 3 + 1);
+}
+
+#[test]
+fn a_gap_at_the_top_exits_on_the_top_edge() {
+    let circle = generate_circle(Radius(15), '#', '.').unwrap();
+    let ValidationResult::EscapePath { path, exit, .. } = validate(&edit(&edit(&circle, 9, 1, '.'), 10, 1, '.')) else {
+        panic!("expected an escape path");
+    };
+    
+    assert_eq!(exit.y(), 0);
+    assert_eq!(path.last(), Some(&exit));
+}
+
+#[test]
+fn a_straight_leak_upwards_is_described_as_north_steps() {
+    let description = describe_escape_path(&edit(THIN_RING, 4, 0, '.'));
+    
+    assert_eq!(description.as_deref(), Some("From the centre go north, north, north, north to reach the top edge."));
+    assert_eq!(describe_escape_path(THIN_RING), None);
+}
+
+#[test]
+fn a_leak_to_the_right_starts_east() {
+    let leaking_right = mirror_horizontal(&gapped_circle());
+    
+    assert_eq!(validate(&leaking_right).first_escape_direction(), Some("east"));
+    assert_eq!(validate(&gapped_circle()).first_escape_direction(), Some("west"));
+    assert_eq!(validate(THIN_RING).first_escape_direction(), None);
+}
@@ -0,0 +1,56 @@
+mod common;
+
+use common::edit;
+use text_circle_validator::*;
+
+fn even_circle(side: usize) -> String {
+    let centre = (side - 1) as f64 / 2.0;
+    
+    (0..side)
+        .map(|y| (0..side).map(|x| if ((x as f64 - centre).hypot(y as f64 - centre) - centre).abs() < 1.0 { '#' } else { '.' }).collect::<String>())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn allow_even() -> ValidatorOptions {
+    ValidatorOptions { allow_even: true, ..Default::default() }
+}
+
+#[test]
+fn even_circles_validate_when_allowed() {
+    for side in [6, 8, 12, 20] {
+        let circle = even_circle(side);
+        
+        assert_eq!(validate(&circle), ValidationResult::NotOdd);
+        assert_eq!(validate_with(&circle, &allow_even()), ValidationResult::Valid { radius: side / 2, foreground: '#', background: '.' }, "side {side}");
+    }
+}
+
+#[test]
+fn an_even_circle_with_a_gap_is_invalid() {
+    let mut circle = even_circle(12);
+    
+    for y in 0..6 {
+        circle = edit(&circle, 6, y, '.');
+    }
+    
+    assert!(!validate_with(&circle, &allow_even()).is_valid());
+}
+
+#[test]
+fn a_4_by_4_square_is_too_small() {
+    for square in ["....\n.##.\n.##.\n....", "####\n#..#\n#..#\n####"] {
+        assert_eq!(validate_with(square, &allow_even()), ValidationResult::RadiusTooSmall(1));
+    }
+}
+
+#[test]
+fn all_issues_reports_an_even_size_and_missing_background_together() {
+    let stray = edit(&even_circle(12), 4, 4, '#');
+    let issues = all_issues(&stray);
+    
+    assert_eq!(issues.len(), 2, "{issues:?}");
+    assert_eq!(issues[0], ValidationResult::NotOdd);
+    assert_eq!(issues[1], ValidationResult::MissingBackground { background: '.', positions: vec![(4, 4)] });
+    assert!(all_issues(&generate_circle(Radius(5), '#', '.').unwrap()).is_empty());
+}
@@ -0,0 +1,18 @@
+mod common;
+
+use common::{edit, THIN_RING};
+use text_circle_validator::*;
+
+#[test]
+fn stray_foreground_inside_is_fixed() {
+    let stray = edit(&edit(THIN_RING, 3, 3, '#'), 5, 6, '#');
+    
+    assert_eq!(validate(&stray), ValidationResult::MissingBackground { background: '.', positions: vec![(3, 3), (5, 6)] });
+    assert_eq!(autofix_background(&stray).unwrap(), THIN_RING);
+    assert!(validate(&autofix_background(&stray).unwrap()).is_valid());
+}
+
+#[test]
+fn malformed_input_cannot_be_fixed() {
+    assert_eq!(autofix_background("ab\ncd"), Err(ValidationError(validate("ab\ncd"))));
+}
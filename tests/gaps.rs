@@ -0,0 +1,22 @@
+mod common;
+
+use common::{edit, THIN_RING};
+use text_circle_validator::*;
+
+#[test]
+fn two_separate_gaps_are_both_listed() {
+    let gapped = edit(&edit(THIN_RING, 7, 1, '.'), 1, 7, '.');
+    let gaps = wall_gaps(&gapped).into_iter().map(|l| (l.x(), l.y())).collect::<Vec<(usize, usize)>>();
+    
+    assert_eq!(gaps, [(7, 1), (1, 7)]);
+    assert!(wall_gaps(THIN_RING).is_empty());
+}
+
+#[test]
+fn gaps_onto_two_edges_list_exits_on_both() {
+    let gapped = edit(&edit(THIN_RING, 7, 1, '.'), 1, 7, '.');
+    let exits = porous_edges(&gapped).into_iter().map(|l| (l.x(), l.y())).collect::<Vec<(usize, usize)>>();
+    
+    assert_eq!(exits, [(7, 0), (8, 0), (8, 1), (0, 7), (0, 8), (1, 8)]);
+    assert!(porous_edges(THIN_RING).is_empty());
+}
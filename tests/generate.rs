@@ -0,0 +1,43 @@
+use text_circle_validator::*;
+
+#[test]
+fn generated_circles_validate_with_their_radius() {
+    for radius in MIN_RADIUS..=10 {
+        let circle = generate_circle(Radius(radius), '#', '.').unwrap();
+        
+        assert_eq!(validate(&circle), ValidationResult::Valid { radius, foreground: '#', background: '.' }, "\n{circle}");
+    }
+}
+
+#[test]
+fn radii_below_the_minimum_are_not_generated() {
+    for radius in 0..MIN_RADIUS {
+        assert_eq!(generate_circle(Radius(radius), '#', '.'), Err(GenerateError::RadiusTooSmall));
+    }
+}
+
+#[test]
+fn the_characters_must_differ() {
+    assert_eq!(generate_circle(Radius(3), '#', '#'), Err(GenerateError::SameCharacters));
+}
+
+#[test]
+fn random_circles_are_the_same_for_the_same_seed() {
+    for seed in [0, 1, 42, u64::MAX] {
+        let first = random_valid_circle(3..12, seed);
+        
+        assert_eq!(random_valid_circle(3..12, seed), first);
+        assert!(validate(&first).is_valid());
+        assert!((3..12).contains(&radius(&first)));
+    }
+    
+    assert!((0..20).map(|seed| radius(&random_valid_circle(3..12, seed))).any(|r| r != radius(&random_valid_circle(3..12, 0))));
+}
+
+#[test]
+fn a_radius_below_the_minimum_cannot_be_made() {
+    assert_eq!(Radius::new(0), None);
+    assert_eq!(Radius::new(MIN_RADIUS - 1), None);
+    assert_eq!(Radius::new(MIN_RADIUS), Some(Radius(MIN_RADIUS)));
+    assert_eq!(Radius(4).side_length(), 9);
+}
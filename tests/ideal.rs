@@ -0,0 +1,59 @@
+use std::collections::HashSet;
+
+use text_circle_validator::*;
+
+#[test]
+fn the_ideal_ring_of_radius_3_is_symmetric() {
+    let ring = ideal_ring_cells(Radius(3));
+    let cells = ring.iter().copied().collect::<HashSet<Location>>();
+    
+    assert_eq!(ring.len(), 32);
+    assert!(ring.iter().all(|l| cells.contains(&Location::new(6 - l.x(), l.y()))));
+    assert!(ring.iter().all(|l| cells.contains(&Location::new(l.x(), 6 - l.y()))));
+}
+
+#[test]
+fn a_perfect_circle_scores_1_and_a_half_erased_one_about_a_half() {
+    let circle = generate_circle(Radius(10), '#', '.').unwrap();
+    let half_erased = circle.lines().map(|row| row.chars().enumerate().map(|(x, c)| if x < 10 { '.' } else { c }).collect::<String>()).collect::<Vec<String>>().join("\n");
+    let score = circle_score(&half_erased).unwrap();
+    
+    assert_eq!(circle_score(&circle), Ok(1.0));
+    assert!((score - 0.5).abs() < 0.05, "{score}");
+}
+
+#[test]
+fn the_centre_is_required_background_and_the_ring_is_in_the_band() {
+    let grid = classification_grid(&generate_circle(Radius(4), '#', '.').unwrap());
+    
+    assert_eq!(grid.len(), 9);
+    assert_eq!(grid[4][4], CellKind::RequiredBackground);
+    assert_eq!(grid[0][4], CellKind::RingBand);
+    assert_eq!(grid[4][0], CellKind::RingBand);
+    assert_eq!(grid[0][0], CellKind::Exterior);
+}
+
+// An outline twice as wide as it is tall, in a 21 by 21 square.
+fn oval() -> String {
+    let on_outline = |x: usize, y: usize| (((x as f64 - 10.0) / 10.0).hypot((y as f64 - 10.0) / 5.0) - 1.0).abs() < 0.1;
+    
+    (0..21)
+        .map(|y| (0..21).map(|x| if on_outline(x, y) { '#' } else { '.' }).collect::<String>())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[test]
+fn an_oval_fits_a_radius_well_below_half_its_height() {
+    let circle = generate_circle(Radius(10), '#', '.').unwrap();
+    
+    assert!((best_fit_radius(&circle) - 10.0).abs() < 0.5, "{}", best_fit_radius(&circle));
+    assert!(best_fit_radius(&oval()) < 9.0, "{}", best_fit_radius(&oval()));
+}
+
+#[test]
+fn radius_3_metrics_match_a_hand_count() {
+    // Within distance 2 of the centre: the centre, its 8 neighbours and the 4 cells 2 away
+    // along the axes. Of the remaining 36 cells only the 4 corners are beyond distance 4.
+    assert_eq!(circle_metrics(Radius(3)), CircleMetrics { side_length: 7, ring_cells: 32, interior_cells: 13 });
+}
@@ -0,0 +1,76 @@
+mod common;
+
+use common::{edit, THIN_RING};
+use text_circle_validator::*;
+
+#[test]
+fn opening_and_closing_a_gap_toggles_between_valid_and_escape() {
+    let mut state = ValidationState::new(THIN_RING).unwrap();
+    assert!(state.result().is_valid());
+    
+    let opened = revalidate_after_edit(&state, 7, 1, '.');
+    assert!(matches!(opened, ValidationResult::EscapePath { .. }), "{opened}");
+    assert_eq!(opened, validate(&edit(THIN_RING, 7, 1, '.')));
+    
+    assert_eq!(state.edit(7, 1, '.'), &opened);
+    assert!(state.edit(7, 1, '#').is_valid());
+    assert_eq!(state.result(), &validate(THIN_RING));
+}
+
+#[test]
+fn every_single_edit_matches_full_validation() {
+    let circle = generate_circle(Radius(6), '#', '.').unwrap();
+    let state = ValidationState::new(&circle).unwrap();
+    let side = height(&circle);
+    
+    for y in 0..side {
+        for x in 0..side {
+            for c in ['#', '.', 'x'] {
+                assert_eq!(revalidate_after_edit(&state, x, y, c), validate(&edit(&circle, x, y, c)), "({x}, {y}) to {c:?}");
+            }
+        }
+    }
+}
+
+#[test]
+fn random_edits_match_full_validation() {
+    for start in [THIN_RING.to_string(), generate_circle(Radius(8), '#', '.').unwrap()] {
+        let mut state = ValidationState::new(&start).unwrap();
+        let mut current = start.clone();
+        let side = height(&start);
+        let mut seed = 1u64;
+        
+        for _ in 0..500 {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let x = (seed >> 33) as usize % side;
+            let y = (seed >> 13) as usize % side;
+            let c = if (seed >> 7).is_multiple_of(2) { '#' } else { '.' };
+            let original = current.lines().nth(y).unwrap().chars().nth(x).unwrap();
+            current = edit(&current, x, y, c);
+            
+            assert_eq!(state.edit(x, y, c), &validate(&current), "({x}, {y}) to {c:?} giving\n{current}");
+            
+            // Undo most edits so the grid keeps returning to a valid circle rather than
+            // drifting into noise.
+            if !(seed >> 3).is_multiple_of(8) {
+                current = edit(&current, x, y, original);
+                assert_eq!(state.edit(x, y, original), &validate(&current), "({x}, {y}) back to {original:?} giving\n{current}");
+            }
+        }
+    }
+}
+
+#[test]
+fn rewriting_a_cell_with_its_own_character_keeps_it_reachable() {
+    let circle = generate_circle(Radius(2), '#', '.').unwrap();
+    let mut state = ValidationState::new(&circle).unwrap();
+    let mut current = circle.clone();
+    
+    for (x, y) in [(2, 1), (1, 2), (1, 1), (1, 0)] {
+        current = edit(&current, x, y, '.');
+        
+        assert_eq!(state.edit(x, y, '.'), &validate(&current), "({x}, {y}) giving\n{current}");
+    }
+    
+    assert!(matches!(state.result(), ValidationResult::EscapePath { .. }), "{:?}", state.result());
+}
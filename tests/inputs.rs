@@ -0,0 +1,80 @@
+use text_circle_validator::*;
+
+fn hand_built_ring() -> Vec<Vec<bool>> {
+    let (o, x) = (false, true);
+    
+    vec![
+        vec![o, o, x, x, x, o, o],
+        vec![o, x, o, o, o, x, o],
+        vec![x, o, o, o, o, o, x],
+        vec![x, o, o, o, o, o, x],
+        vec![x, o, o, o, o, o, x],
+        vec![o, x, o, o, o, x, o],
+        vec![o, o, x, x, x, o, o],
+    ]
+}
+
+#[test]
+fn a_boolean_ring_validates() {
+    assert_eq!(validate_grid(&hand_built_ring()), ValidationResult::Valid { radius: 3, foreground: '#', background: '.' });
+}
+
+#[test]
+fn a_gapped_boolean_ring_has_an_escape_path() {
+    let mut gapped = hand_built_ring();
+    gapped[1][1] = false;
+    
+    let result = validate_grid(&gapped);
+    assert!(matches!(result, ValidationResult::EscapePath { .. }), "{result:?}");
+}
+
+#[test]
+fn circles_separated_by_blank_lines_are_validated_separately() {
+    let small = generate_circle(Radius(2), '#', '.').unwrap();
+    let large = generate_circle(Radius(4), 'O', ' ').unwrap();
+    let invalid = "###\n###\n###";
+    let results = validate_many(&format!("{small}\n\n{large}\n\n{invalid}\n"));
+    
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0], ValidationResult::Valid { radius: 2, foreground: '#', background: '.' });
+    assert_eq!(results[1], ValidationResult::Valid { radius: 4, foreground: 'O', background: ' ' });
+    assert!(!results[2].is_valid());
+}
+
+#[test]
+fn a_space_backed_circle_with_trailing_spaces_removed_is_padded() {
+    let circle = generate_circle(Radius(4), 'O', ' ').unwrap();
+    let trimmed = circle.lines().map(str::trim_end).collect::<Vec<&str>>().join("\n");
+    let padded = ValidatorOptions { pad_rows: true, ..Default::default() };
+    
+    assert!(matches!(validate(&trimmed), ValidationResult::NotSquare { .. }));
+    assert_eq!(validate_with(&trimmed, &padded), ValidationResult::Valid { radius: 4, foreground: 'O', background: ' ' });
+}
+
+#[test]
+fn rows_of_a_valid_circle_validate_directly() {
+    let circle = generate_circle(Radius(5), '#', '.').unwrap();
+    let rows = circle.lines().collect::<Vec<&str>>();
+    
+    assert_eq!(validate_rows(&rows), ValidationResult::Valid { radius: 5, foreground: '#', background: '.' });
+    assert_eq!(validate_rows(&rows[1..]), validate(&rows[1..].join("\n")));
+}
+
+#[test]
+fn a_tab_prefixed_circle_validates_once_tabs_are_expanded() {
+    let circle = generate_circle(Radius(4), 'O', ' ').unwrap();
+    let tabbed = circle.lines().map(|row| row.replacen(' ', "\t", 1)).collect::<Vec<String>>().join("\n");
+    let expanded = ValidatorOptions { tab_width: 1, ..Default::default() };
+    
+    assert!(!validate(&tabbed).is_valid());
+    assert_eq!(validate_with(&tabbed, &expanded), ValidationResult::Valid { radius: 4, foreground: 'O', background: ' ' });
+}
+
+#[test]
+fn byte_rows_of_a_valid_circle_validate() {
+    let circle = generate_circle(Radius(5), '#', '.').unwrap();
+    let rows = circle.lines().map(str::as_bytes).collect::<Vec<&[u8]>>();
+    
+    assert_eq!(validate_bytes(&rows), ValidationResult::Valid { radius: 5, foreground: '#', background: '.' });
+    assert_eq!(validate_bytes(&rows[..10]), validate(&circle.lines().take(10).collect::<Vec<&str>>().join("\n")));
+}
@@ -0,0 +1,27 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+use common::{edit, THIN_RING};
+use text_circle_validator::*;
+
+#[test]
+fn a_valid_circle_reports_its_radius() {
+    assert_eq!(validate_json(THIN_RING), r#"{"valid":true,"reason":"valid","radius":4}"#);
+}
+
+#[test]
+fn missing_background_reports_positions_as_pairs() {
+    let stray = edit(&edit(THIN_RING, 4, 2, '#'), 4, 3, '#');
+    
+    assert_eq!(validate_json(&stray), r#"{"valid":false,"reason":"missing_background","character":".","position_count":2,"positions":[[4,2],[4,3]]}"#);
+}
+
+#[test]
+fn an_escape_path_is_an_array_of_locations() {
+    let circle = generate_circle(Radius(15), '#', '.').unwrap();
+    let json = validate_json(&edit(&edit(&circle, 0, 12, '.'), 1, 12, '.'));
+    
+    assert!(json.starts_with(r#"{"valid":false,"reason":"escape_path","path":[[15,15],"#), "{json}");
+    assert!(json.ends_with(r#",[0,12]],"exit":[0,12],"first_direction":"west"}"#), "{json}");
+}
@@ -0,0 +1,39 @@
+mod common;
+
+use std::collections::HashSet;
+
+use common::edit;
+use text_circle_validator::*;
+
+// The number of times the escape path search takes a cell from its queue, which stays the same
+// however fast the machine running the tests is.
+fn search_steps(s: &str) -> usize {
+    escape_search_trace(s).len()
+}
+
+#[test]
+fn a_large_circle_searches_each_cell_at_most_once() {
+    let circle = generate_circle(Radius(100), '#', '.').unwrap();
+    let gapped = edit(&edit(&circle, 0, 90, '.'), 1, 90, '.');
+    
+    assert_eq!(validate(&circle), ValidationResult::Valid { radius: 100, foreground: '#', background: '.' });
+    assert!(matches!(validate(&gapped), ValidationResult::EscapePath { .. }));
+    
+    for input in [&circle, &gapped] {
+        let trace = escape_search_trace(input);
+        
+        assert_eq!(trace.iter().collect::<HashSet<&Location>>().len(), trace.len());
+    }
+    
+    assert!(search_steps(&gapped) < search_steps(&circle), "{} steps with a gap", search_steps(&gapped));
+}
+
+#[test]
+fn a_sealed_radius_30_circle_is_searched_once_through_its_inside() {
+    let circle = generate_circle(Radius(30), '#', '.').unwrap();
+    let inside = reachable_background(&circle, Location::new(30, 30), Connectivity::default());
+    
+    assert_eq!(reaches_edge_from(&circle, 30, 30), Ok(false));
+    assert_eq!(validate(&circle).escape_path(), None);
+    assert_eq!(search_steps(&circle), inside.len());
+}
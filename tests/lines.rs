@@ -0,0 +1,35 @@
+mod common;
+
+use common::{edit, filled_disk};
+use text_circle_validator::*;
+
+fn assert_lines_match(s: &str) {
+    assert_eq!(validate_lines(s.lines()), validate(s));
+}
+
+#[test]
+fn a_large_circle_validates_the_same_line_by_line() {
+    let circle = generate_circle(Radius(200), '#', '.').unwrap();
+    
+    assert_lines_match(&circle);
+    assert!(validate_lines(circle.lines()).is_valid());
+}
+
+#[test]
+fn a_large_invalid_circle_gives_the_same_result_line_by_line() {
+    let circle = generate_circle(Radius(200), '#', '.').unwrap();
+    
+    assert_lines_match(&edit(&circle, 200, 0, '.'));
+    assert_lines_match(&edit(&circle, 0, 0, '#'));
+    assert_lines_match(&edit(&circle, 200, 200, '#'));
+    assert_lines_match(&edit(&circle, 3, 150, 'x'));
+    assert_lines_match(&circle.lines().skip(1).collect::<Vec<&str>>().join("\n"));
+    assert_lines_match(&filled_disk(40));
+}
+
+#[test]
+fn trailing_empty_lines_are_ignored() {
+    let circle = generate_circle(Radius(5), '#', '.').unwrap();
+    
+    assert_eq!(validate_lines(circle.lines().chain(["", ""])), validate(&circle));
+}
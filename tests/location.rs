@@ -0,0 +1,41 @@
+use std::collections::HashSet;
+
+use text_circle_validator::*;
+
+#[test]
+fn locations_deduplicate_in_a_hash_set() {
+    let locations = [Location::new(1, 2), Location::new(2, 1), Location::new(1, 2)].into_iter().collect::<HashSet<Location>>();
+    
+    assert_eq!(locations.len(), 2);
+    assert!(locations.contains(&Location::new(2, 1)));
+    assert_eq!(format!("{:?}", Location::new(1, 2)), "Location { x: 1, y: 2 }");
+}
+
+#[test]
+fn euclidean_distance_is_the_straight_line_distance() {
+    assert_eq!(Location::new(0, 0).euclidean_distance(&Location::new(3, 4)), 5.0);
+    assert_eq!(Location::new(3, 4).euclidean_distance(&Location::new(0, 0)), 5.0);
+}
+
+#[test]
+fn a_centre_cell_has_4_orthogonal_and_8_diagonal_neighbours() {
+    let centre = Location::new(1, 1);
+    let around = |offsets: &[(usize, usize)]| offsets.iter().map(|&(x, y)| Location::new(x, y)).collect::<Vec<Location>>();
+    
+    assert_eq!(neighbours(centre, Connectivity::Orthogonal), around(&[(1, 0), (0, 1), (2, 1), (1, 2)]));
+    assert_eq!(neighbours(centre, Connectivity::Diagonal), around(&[(0, 0), (1, 0), (2, 0), (0, 1), (2, 1), (0, 2), (1, 2), (2, 2)]));
+}
+
+#[test]
+fn edge_cells_have_no_neighbours_off_the_grid() {
+    assert_eq!(neighbours(Location::new(0, 0), Connectivity::Orthogonal), vec![Location::new(1, 0), Location::new(0, 1)]);
+    assert_eq!(neighbours(Location::new(0, 0), Connectivity::Diagonal).len(), 3);
+    assert_eq!(neighbours(Location::new(3, 0), Connectivity::Diagonal).len(), 5);
+}
+
+#[test]
+fn offsets_below_zero_are_none() {
+    assert_eq!(Location::new(0, 0).offset(-1, 0), None);
+    assert_eq!(Location::new(0, 0).offset(0, -1), None);
+    assert_eq!(Location::new(2, 3).offset(-2, 1), Some(Location::new(0, 4)));
+}
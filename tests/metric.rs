@@ -0,0 +1,23 @@
+use text_circle_validator::*;
+
+fn manhattan() -> ValidatorOptions {
+    ValidatorOptions { metric: DistanceMetric::Manhattan, ..Default::default() }
+}
+
+#[test]
+fn a_generated_diamond_validates_with_the_manhattan_metric() {
+    for radius in [3, 6, 10] {
+        let diamond = generate_circle_with_metric(Radius(radius), '#', '.', DistanceMetric::Manhattan).unwrap();
+        
+        assert_eq!(validate_with(&diamond, &manhattan()), ValidationResult::Valid { radius, foreground: '#', background: '.' }, "\n{diamond}");
+    }
+}
+
+#[test]
+fn a_diamond_has_its_corners_on_the_axes() {
+    let diamond = generate_circle_with_metric(Radius(3), '#', '.', DistanceMetric::Manhattan).unwrap();
+    
+    assert_eq!(char_at(&diamond, 3, 0), Some('#'));
+    assert_eq!(char_at(&diamond, 1, 1), Some('.'));
+    assert!(!validate_with(&generate_circle(Radius(3), '#', '.').unwrap(), &manhattan()).is_valid());
+}
@@ -0,0 +1,34 @@
+use text_circle_validator::*;
+
+fn circle() -> String {
+    generate_circle(Radius(3), '#', '.').unwrap()
+}
+
+fn not_square(height: usize, min_width: usize, max_width: usize, jagged_rows: Vec<(usize, usize)>) -> ValidationResult {
+    ValidationResult::NotSquare { dimensions: Dimensions { height, min_width, max_width }, jagged_rows }
+}
+
+#[test]
+fn rows_that_are_too_wide_report_the_width_and_height() {
+    let wide = circle().lines().map(|row| format!("{row}.")).collect::<Vec<String>>().join("\n");
+    
+    assert_eq!(validate(&wide), not_square(7, 8, 8, vec![]));
+    assert!(validate(&wide).to_string().ends_with("The height is 7 but the width is 8."));
+}
+
+#[test]
+fn a_column_that_is_too_short_reports_the_width_and_height() {
+    let short = circle().lines().take(6).collect::<Vec<&str>>().join("\n");
+    
+    assert_eq!(validate(&short), not_square(6, 7, 7, vec![]));
+    assert!(validate(&short).to_string().ends_with("The height is 6 but the width is 7."));
+}
+
+#[test]
+fn one_short_row_among_many_is_listed_alone() {
+    let circle = generate_circle(Radius(10), '#', '.').unwrap();
+    let jagged = circle.lines().enumerate().map(|(y, row)| if y == 13 { &row[1..] } else { row }).collect::<Vec<&str>>().join("\n");
+    
+    assert_eq!(validate(&jagged), not_square(21, 20, 21, vec![(13, 20)]));
+    assert_eq!(jagged_rows(&jagged), vec![(13, 20)]);
+}
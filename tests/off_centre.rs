@@ -0,0 +1,29 @@
+mod common;
+
+use common::{edit, THIN_RING};
+use text_circle_validator::*;
+
+// THIN_RING in the top left corner of a 15 by 15 field.
+fn in_corner(ring: &str) -> String {
+    cells(ring).fold(["..............."; 15].join("\n"), |field, (x, y, c)| edit(&field, x, y, c))
+}
+
+#[test]
+fn a_circle_in_the_corner_of_a_larger_field_validates() {
+    assert_eq!(validate_with_centre(&in_corner(THIN_RING), 4, 4, Radius(4)), Ok(ValidationResult::Valid { radius: 4, foreground: '#', background: '.' }));
+}
+
+#[test]
+fn positions_are_given_in_the_whole_field() {
+    let stray = edit(&in_corner(THIN_RING), 12, 12, '#');
+    let gapped = in_corner(&edit(THIN_RING, 7, 1, '.'));
+    
+    assert_eq!(validate_with_centre(&stray, 4, 4, Radius(4)), Ok(ValidationResult::MissingBackground { background: '.', positions: vec![(12, 12)] }));
+    assert_eq!(validate_with_centre(&gapped, 4, 4, Radius(4)).unwrap().escape_path().unwrap()[0], Location::new(4, 4));
+}
+
+#[test]
+fn the_circle_must_fit_inside_the_field() {
+    assert_eq!(validate_with_centre(&in_corner(THIN_RING), 3, 4, Radius(4)), Err(SeedError::OutsideGrid));
+    assert_eq!(validate_with_centre(&in_corner(THIN_RING), 11, 11, Radius(4)), Err(SeedError::OutsideGrid));
+}
@@ -0,0 +1,39 @@
+mod common;
+
+use common::edit;
+use text_circle_validator::*;
+
+#[test]
+fn the_top_left_cell_is_described_as_1_1() {
+    let circle = edit(&generate_circle(Radius(6), '#', '.').unwrap(), 0, 0, '#');
+    let result = validate(&circle);
+    
+    assert_eq!(result, ValidationResult::MissingBackground { background: '.', positions: vec![(0, 0)] });
+    assert_eq!(result.one_based(), ValidationResult::MissingBackground { background: '.', positions: vec![(1, 1)] });
+    assert!(result.describe(CoordinateOrder::XY, DiagramFormat::PlainText, None, Indexing::OneBased).ends_with("from (1, 1) at left top should be background character \".\":\n(1, 1)"));
+    assert!(result.describe(CoordinateOrder::XY, DiagramFormat::PlainText, None, Indexing::ZeroBased).ends_with("\n(0, 0)"));
+}
+
+#[test]
+fn row_numbers_of_jagged_rows_are_shifted_too() {
+    let result = validate("...\n..\n...");
+    
+    assert_eq!(result.one_based(), ValidationResult::NotSquare { dimensions: Dimensions { height: 3, min_width: 2, max_width: 3 }, jagged_rows: vec![(2, 2)] });
+    assert!(result.describe(CoordinateOrder::XY, DiagramFormat::PlainText, None, Indexing::OneBased).ends_with("\n(2, 2)"));
+}
+
+#[test]
+fn escape_paths_are_shifted_but_their_diagrams_are_not() {
+    let result = validate(&edit(common::THIN_RING, 7, 1, '.'));
+    let ValidationResult::EscapePath { path, exit, diagram, radius } = &result else {
+        panic!("expected an escape path, got {result:?}");
+    };
+    let ValidationResult::EscapePath { path: shifted_path, exit: shifted_exit, diagram: shifted_diagram, radius: shifted_radius } = result.one_based() else {
+        panic!("expected an escape path");
+    };
+    
+    assert_eq!(shifted_path.iter().map(|l| (l.x(), l.y())).collect::<Vec<_>>(), path.iter().map(|l| (l.x() + 1, l.y() + 1)).collect::<Vec<_>>());
+    assert_eq!((shifted_exit.x(), shifted_exit.y()), (exit.x() + 1, exit.y() + 1));
+    assert_eq!(&shifted_diagram, diagram);
+    assert_eq!(&shifted_radius, radius);
+}
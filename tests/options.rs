@@ -0,0 +1,10 @@
+use text_circle_validator::*;
+
+#[test]
+fn default_options_validate_a_good_circle() {
+    let circle = generate_circle(Radius(5), '#', '.').unwrap();
+    let options = ValidatorOptions::default();
+    
+    assert_eq!(validate_with(&circle, &options), ValidationResult::Valid { radius: 5, foreground: '#', background: '.' });
+    assert_eq!(validate_with(&circle, &options), validate(&circle));
+}
@@ -0,0 +1,29 @@
+use text_circle_validator::*;
+
+#[test]
+fn empty_input_is_a_parse_error() {
+    assert_eq!(parse(""), Err(ParseError::Empty));
+    assert_eq!(parse("\n\n"), Err(ParseError::Empty));
+}
+
+#[test]
+fn ragged_rows_are_a_parse_error() {
+    let dimensions = Dimensions { height: 3, min_width: 2, max_width: 3 };
+    
+    assert_eq!(parse("abc\nab\nabc"), Err(ParseError::RaggedRows { dimensions, jagged_rows: vec![(1, 2)] }));
+    assert_eq!(parse("ab\nab\nab"), Err(ParseError::NotSquare(Dimensions { height: 3, min_width: 2, max_width: 2 })));
+}
+
+#[test]
+fn grid_at_matches_char_at_across_the_whole_grid() {
+    let circle = generate_circle(Radius(5), '●', ' ').unwrap();
+    let grid = parse(&circle).unwrap();
+    
+    assert_eq!(grid.size(), 11);
+    
+    for y in 0..=11 {
+        for x in 0..=11 {
+            assert_eq!(grid.at(x, y), char_at(&circle, x, y), "({x}, {y})");
+        }
+    }
+}
@@ -0,0 +1,31 @@
+mod common;
+
+use common::{edit, THIN_RING};
+use text_circle_validator::*;
+
+// A gapped ring that already uses `#`, `X` and `.`.
+fn gapped_with_x() -> String {
+    edit(&edit(THIN_RING, 7, 1, '.'), 1, 7, 'X')
+}
+
+fn paved_cells(options: &ValidatorOptions) -> Vec<char> {
+    let ValidationResult::EscapePath { path, diagram, .. } = validate_with(&gapped_with_x(), options) else {
+        panic!("expected an escape path");
+    };
+    
+    path.iter().map(|l| diagram[l.y()].chars().nth(l.x()).unwrap()).collect()
+}
+
+#[test]
+fn the_path_is_paved_with_a_character_not_in_the_input() {
+    let options = ValidatorOptions { allow_extra_foreground: true, ..Default::default() };
+    
+    assert!(paved_cells(&options).iter().all(|&c| c == '*'));
+}
+
+#[test]
+fn the_path_is_paved_with_the_chosen_character() {
+    let options = ValidatorOptions { allow_extra_foreground: true, paving: Some('@'), ..Default::default() };
+    
+    assert!(paved_cells(&options).iter().all(|&c| c == '@'));
+}
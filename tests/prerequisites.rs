@@ -0,0 +1,32 @@
+use text_circle_validator::*;
+
+#[test]
+fn squares_too_small_to_hold_a_circle_are_rejected() {
+    assert_eq!(validate("#"), ValidationResult::RadiusTooSmall(0));
+    assert_eq!(validate(".#.\n#.#\n.#."), ValidationResult::RadiusTooSmall(1));
+    assert_eq!(validate_text_circle(".#.\n#.#\n.#."), "Invalid. Radius too small to define a circle.");
+}
+
+#[test]
+fn looks_like_circle_checks_each_prerequisite() {
+    let circle = generate_circle(Radius(3), '#', '.').unwrap();
+    
+    assert!(looks_like_circle(&circle));
+    assert!(looks_like_circle(&format!("{circle}\n")));
+    assert!(!looks_like_circle(""));
+    assert!(!looks_like_circle(&circle.replacen('\n', ".\n", 1)));
+    assert!(!looks_like_circle("#..#\n....\n....\n#..#"));
+    assert!(!looks_like_circle(&circle.replace('#', ".")));
+    assert!(!looks_like_circle(&circle.replacen('#', "+", 1)));
+}
+
+#[test]
+fn uniform_and_three_character_grids_are_reported() {
+    let uniform = ["........."; 9].join("\n");
+    let three = generate_circle(Radius(4), '#', '.').unwrap().replacen('#', "+", 1);
+    
+    assert_eq!(validate(&uniform), ValidationResult::TooFewCharacters(1));
+    assert!(validate(&uniform).to_string().contains("only 1 distinct character"));
+    assert_eq!(validate(&three), ValidationResult::TooManyCharacters(3));
+    assert!(validate(&three).to_string().contains("3 distinct characters"));
+}
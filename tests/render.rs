@@ -0,0 +1,52 @@
+mod common;
+
+use common::THIN_RING;
+use text_circle_validator::*;
+
+#[test]
+fn svg_draws_a_rect_for_each_foreground_cell() {
+    let svg = render_svg(THIN_RING, 10).unwrap();
+    
+    assert_eq!(svg.matches("<rect ").count(), 24);
+    assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="90" height="90""#), "{svg}");
+}
+
+#[test]
+fn svg_grid_lines_are_drawn_between_every_cell() {
+    let svg = render_svg_with_grid_lines(THIN_RING, 10).unwrap();
+    
+    assert_eq!(svg.matches("<rect ").count(), 24);
+    assert_eq!(svg.matches("<line ").count(), 20);
+}
+
+#[test]
+fn svg_rejects_a_zero_cell_size() {
+    let circle = generate_circle(Radius(3), '#', '.').unwrap();
+    
+    assert_eq!(render_svg(&circle, 0), Err(RenderError::ZeroScale));
+    assert_eq!(render_svg_with_grid_lines(&circle, 0), Err(RenderError::ZeroScale));
+}
+
+#[test]
+fn svg_reports_an_invalid_circle_before_the_cell_size() {
+    assert_eq!(render_svg("###\n#.#\n###", 0), Err(RenderError::Invalid(ValidationError(ValidationResult::RadiusTooSmall(1)))));
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn png_is_the_side_length_times_the_scale() {
+    let png = render_png(THIN_RING, 3).unwrap();
+    let image = image::load_from_memory_with_format(&png, image::ImageFormat::Png).unwrap().into_luma8();
+    
+    assert_eq!(image.dimensions(), (27, 27));
+    assert_eq!(image.get_pixel(0, 0).0, [255]);
+    assert_eq!(image.get_pixel(6, 0).0, [0]);
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn png_rejects_a_zero_scale() {
+    let circle = generate_circle(Radius(3), '#', '.').unwrap();
+    
+    assert_eq!(render_png(&circle, 0), Err(RenderError::ZeroScale));
+}
@@ -0,0 +1,24 @@
+use text_circle_validator::*;
+
+const RADIUS_3: &str = "..###..\n.#...#.\n#.....#\n#.....#\n#.....#\n.#...#.\n..###..";
+
+#[test]
+fn outcomes_can_be_matched() {
+    assert_eq!(validate(RADIUS_3), ValidationResult::Valid { radius: 3, foreground: '#', background: '.' });
+    assert_eq!(validate(""), ValidationResult::Empty);
+    assert!(matches!(validate("...\n..."), ValidationResult::NotSquare { .. }));
+    assert_eq!(validate("....\n....\n....\n...."), ValidationResult::NotOdd);
+    assert_eq!(validate(&RADIUS_3.replace('#', ".")), ValidationResult::TooFewCharacters(1));
+}
+
+#[test]
+fn the_wrapper_displays_the_result() {
+    let stray = RADIUS_3.replacen("...", ".#.", 2);
+    
+    for input in [RADIUS_3, "", "...\n...", &stray] {
+        assert_eq!(validate_text_circle(input), validate(input).to_string());
+    }
+    
+    assert_eq!(validate_text_circle(RADIUS_3), "This is a valid text circle of radius 3.");
+    assert_eq!(validate_text_circle(""), "Invalid. The input is empty.");
+}
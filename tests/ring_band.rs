@@ -0,0 +1,16 @@
+mod common;
+
+use common::{edit, THIN_RING};
+use text_circle_validator::*;
+
+#[test]
+fn a_blank_ring_is_missing_foreground() {
+    let blank = edit(&["........."; 9].join("\n"), 2, 0, '#');
+    
+    assert_eq!(validate(&blank), ValidationResult::MissingForeground { foreground: '#', positions: vec![(4, 0), (0, 4), (8, 4), (4, 8)] });
+}
+
+#[test]
+fn a_gap_in_the_ring_is_missing_foreground() {
+    assert_eq!(validate(&edit(THIN_RING, 4, 0, '.')), ValidationResult::MissingForeground { foreground: '#', positions: vec![(4, 0)] });
+}
@@ -0,0 +1,40 @@
+mod common;
+
+use common::{edit, THIN_RING};
+use text_circle_validator::*;
+
+fn sealed(s: &str, seal: &[Location]) -> String {
+    seal.iter().fold(s.to_string(), |sealed, location| edit(&sealed, location.x(), location.y(), '#'))
+}
+
+#[test]
+fn a_sealed_circle_needs_nothing() {
+    assert!(suggest_seal(THIN_RING).is_empty());
+    assert!(suggest_seal(&generate_circle(Radius(8), '#', '.').unwrap()).is_empty());
+}
+
+#[test]
+fn a_single_gap_is_sealed_with_one_cell() {
+    let gapped = edit(THIN_RING, 7, 1, '.');
+    
+    assert_eq!(suggest_seal(&gapped), vec![Location::new(7, 1)]);
+}
+
+#[test]
+fn two_gaps_are_sealed_with_one_cell_each() {
+    let gapped = edit(&edit(THIN_RING, 7, 1, '.'), 1, 7, '.');
+    let seal = suggest_seal(&gapped);
+    
+    assert_eq!(seal, vec![Location::new(7, 1), Location::new(1, 7)]);
+    assert!(validate(&sealed(&gapped, &seal)).is_valid());
+}
+
+#[test]
+fn a_wide_gap_is_sealed_at_its_narrowest() {
+    let circle = generate_circle(Radius(20), '#', '.').unwrap();
+    let gapped = (0..4).fold(circle, |gapped, y| edit(&edit(&gapped, 20, y, '.'), 21, y, '.'));
+    let seal = suggest_seal(&gapped);
+    
+    assert!(!seal.is_empty() && seal.len() <= 3, "{seal:?}");
+    assert_eq!(reaches_edge_from(&sealed(&gapped, &seal), 20, 20), Ok(false));
+}
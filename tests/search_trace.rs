@@ -0,0 +1,20 @@
+mod common;
+
+use common::{edit, THIN_RING};
+use text_circle_validator::*;
+
+#[test]
+fn the_search_starts_at_the_centre() {
+    let trace = escape_search_trace(THIN_RING);
+    
+    assert_eq!(trace.first(), Some(&Location::new(4, 4)));
+}
+
+#[test]
+fn the_search_ends_at_the_edge_when_there_is_a_way_out() {
+    let trace = escape_search_trace(&edit(THIN_RING, 7, 1, '.'));
+    
+    assert_eq!(trace.first(), Some(&Location::new(4, 4)));
+    let last = trace.last().unwrap();
+    assert!(last.x() == 0 || last.y() == 0 || last.x() == 8 || last.y() == 8, "{last:?}");
+}
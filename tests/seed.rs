@@ -0,0 +1,34 @@
+mod common;
+
+use common::{edit, THIN_RING};
+use text_circle_validator::*;
+
+#[test]
+fn an_off_centre_seed_inside_a_sealed_ring_does_not_reach_the_edge() {
+    assert_eq!(reaches_edge_from(THIN_RING, 2, 3), Ok(false));
+    assert_eq!(reaches_edge_from(THIN_RING, 6, 6), Ok(false));
+}
+
+#[test]
+fn an_off_centre_seed_inside_a_gapped_ring_reaches_the_edge() {
+    assert_eq!(reaches_edge_from(&edit(THIN_RING, 7, 1, '.'), 2, 6), Ok(true));
+}
+
+#[test]
+fn the_seed_must_be_background_inside_the_square() {
+    assert_eq!(reaches_edge_from(THIN_RING, 0, 4), Err(SeedError::NotBackground));
+    assert_eq!(reaches_edge_from(THIN_RING, 9, 4), Err(SeedError::OutsideGrid));
+    assert_eq!(reaches_edge_from("..\n.", 0, 0), Err(SeedError::NotSquare));
+}
+
+#[test]
+fn the_background_reachable_from_the_centre_of_a_sealed_circle_is_its_interior() {
+    for radius in [4, 8, 13] {
+        let circle = generate_circle(Radius(radius), '#', '.').unwrap();
+        let reachable = reachable_background(&circle, Location::new(radius, radius), Connectivity::default());
+        
+        assert_eq!(reachable.len(), circle_metrics(Radius(radius)).interior_cells, "radius {radius}");
+    }
+    
+    assert!(reachable_background(THIN_RING, Location::new(0, 4), Connectivity::default()).is_empty());
+}
@@ -0,0 +1,54 @@
+mod common;
+
+use common::{edit, THIN_RING};
+use text_circle_validator::*;
+
+#[test]
+fn a_rotated_circle_is_valid_with_the_same_radius() {
+    for radius in [3, 6, 11] {
+        let circle = generate_circle(Radius(radius), '#', '.').unwrap();
+        
+        assert_eq!(validate(&rotate_90(&circle)), validate(&circle));
+        assert_eq!(validate(&mirror_horizontal(&circle)), validate(&circle));
+    }
+}
+
+#[test]
+fn rotating_and_mirroring_move_cells() {
+    assert_eq!(rotate_90("ab\ncd"), "ca\ndb");
+    assert_eq!(mirror_horizontal("ab\ncd"), "ba\ndc");
+    assert_eq!(rotate_90(&rotate_90(&rotate_90(&rotate_90(THIN_RING)))), THIN_RING);
+    assert_eq!(rotate_90(&edit(THIN_RING, 7, 1, '.')), edit(THIN_RING, 7, 7, '.'));
+}
+
+#[test]
+fn an_asymmetric_valid_ring_lists_its_mismatches() {
+    let lopsided = edit(THIN_RING, 2, 1, '#');
+    let report = symmetry_report(&lopsided);
+    
+    assert!(validate(&lopsided).is_valid());
+    assert!(!report.is_symmetric());
+    assert_eq!(report.vertical, vec![(2, 1), (6, 1)]);
+    assert_eq!(report.horizontal, vec![(2, 1), (2, 7)]);
+    assert_eq!(report.diagonal, vec![(2, 1), (1, 2)]);
+    assert!(symmetry_report(THIN_RING).is_symmetric());
+}
+
+#[test]
+fn circles_of_the_same_shape_canonicalize_identically() {
+    let spaced = generate_circle(Radius(5), 'O', ' ').unwrap();
+    let hashed = generate_circle(Radius(5), '#', '.').unwrap();
+    
+    assert_eq!(canonicalize(&spaced), canonicalize(&hashed));
+    assert!(canonicalize(&spaced).is_ok());
+    assert_ne!(canonicalize(&hashed), canonicalize(&generate_circle(Radius(4), '#', '.').unwrap()));
+}
+
+#[test]
+fn a_square_has_4_hull_vertices_and_a_circle_many() {
+    let square = ["#######", "#.....#", "#.....#", "#.....#", "#.....#", "#.....#", "#######"].join("\n");
+    let hull = foreground_hull(&square).into_iter().map(|l| (l.x(), l.y())).collect::<Vec<(usize, usize)>>();
+    
+    assert_eq!(hull, [(0, 0), (0, 6), (6, 6), (6, 0)]);
+    assert!(foreground_hull(&generate_circle(Radius(10), '#', '.').unwrap()).len() > 12);
+}
@@ -0,0 +1,39 @@
+mod common;
+
+use common::{edit, THIN_RING};
+use text_circle_validator::*;
+
+#[test]
+fn ring_thickness_counts_in_from_the_left_edge() {
+    let one_cell = generate_circle(Radius(6), '#', '.').unwrap();
+    let two_cell = edit(&one_cell, 1, 6, '#');
+    
+    assert_eq!(ring_thickness(&one_cell), 1);
+    assert_eq!(ring_thickness(&two_cell), 2);
+    assert!(!validate(&two_cell).is_valid());
+}
+
+#[test]
+fn a_two_cell_thick_wall_lists_the_outer_cells() {
+    let thick = edit(&edit(THIN_RING, 1, 3, '#'), 7, 5, '#');
+    
+    assert_eq!(assert_thin_ring(THIN_RING), Ok(()));
+    assert_eq!(assert_thin_ring(&thick), Err(vec![Location::new(0, 3), Location::new(8, 5)]));
+}
+
+// A radius 8 ring at least 2 cells thick everywhere.
+fn thick_ring() -> String {
+    let on_ring = |x: usize, y: usize| (6.4..8.5).contains(&(x as f64 - 8.0).hypot(y as f64 - 8.0));
+    
+    (0..17)
+        .map(|y| (0..17).map(|x| if on_ring(x, y) { '#' } else { '.' }).collect::<String>())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[test]
+fn the_thinnest_point_of_the_wall_is_found() {
+    assert_eq!(min_wall_thickness(&thick_ring()), 2);
+    assert_eq!(min_wall_thickness(&edit(&thick_ring(), 1, 8, '.')), 1);
+    assert_eq!(min_wall_thickness(&edit(&edit(&thick_ring(), 1, 8, '.'), 0, 8, '.')), 0);
+}
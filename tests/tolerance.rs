@@ -0,0 +1,24 @@
+use text_circle_validator::*;
+
+#[test]
+fn a_tighter_tolerance_flags_cells_the_default_accepts() {
+    let circle = generate_circle(Radius(6), '#', '.').unwrap();
+    let tight = ValidatorOptions { tolerance: RingTolerance { inner: 0.5, outer: 0.5 }, ..Default::default() };
+    
+    assert!(validate_with(&circle, &ValidatorOptions::default()).is_valid());
+    
+    let ValidationResult::MissingBackground { positions, .. } = validate_with(&circle, &tight) else {
+        panic!("expected missing background");
+    };
+    assert!(!positions.is_empty());
+}
+
+#[test]
+fn a_looser_tolerance_accepts_what_the_default_flags() {
+    let loose = ValidatorOptions { tolerance: RingTolerance { inner: 1.5, outer: 1.0 }, ..Default::default() };
+    let circle = generate_circle(Radius(6), '#', '.').unwrap();
+    let thick = circle.lines().enumerate().map(|(y, row)| if y == 6 { "##.........##" } else { row }).collect::<Vec<&str>>().join("\n");
+    
+    assert!(matches!(validate(&thick), ValidationResult::MissingBackground { .. }));
+    assert!(validate_with(&thick, &loose).is_valid());
+}
@@ -0,0 +1,67 @@
+use text_circle_validator::*;
+
+fn with_trailing_newlines() -> Vec<(String, String)> {
+    [2, 5].into_iter()
+        .map(|radius| generate_circle(Radius(radius), '#', '.').unwrap())
+        .flat_map(|circle| ["\n", "\n\n", "\r\n\r\n"].map(|ending| (circle.clone(), format!("{circle}{ending}"))))
+        .collect()
+}
+
+#[test]
+fn trailing_newlines_are_not_reported_as_not_square() {
+    for (circle, padded) in with_trailing_newlines() {
+        assert_eq!(validate(&padded), validate(&circle));
+        assert!(validate(&padded).is_valid());
+    }
+}
+
+#[test]
+fn interior_blank_lines_are_still_errors() {
+    let circle = generate_circle(Radius(2), '#', '.').unwrap().replacen('\n', "\n\n", 1);
+    
+    assert_eq!(validate(&circle).reason(), "not_square");
+}
+
+#[test]
+fn mixed_line_endings_validate() {
+    let circle = generate_circle(Radius(4), '#', '.').unwrap();
+    let mixed = circle.lines().enumerate().map(|(y, row)| format!("{row}{}", ["\n", "\r\n", "\r\r\n"][y % 3])).collect::<String>();
+    
+    assert_eq!(normalize_line_endings(&mixed), format!("{circle}\n"));
+    assert_eq!(validate(&mixed), validate(&circle));
+    assert!(validate(&mixed).is_valid());
+}
+
+#[test]
+fn helpers_see_the_same_grid_as_validate() {
+    for (circle, padded) in with_trailing_newlines() {
+        assert_eq!(height(&padded), height(&circle));
+        assert_eq!(radius(&padded), radius(&circle));
+        assert_eq!(background_character(&padded), Some('.'));
+        assert_eq!(foreground_count(&padded), foreground_count(&circle));
+        assert_eq!(background_confidence(&padded), 1.0);
+        assert_eq!(classification_grid(&padded), classification_grid(&circle));
+        assert_eq!(jagged_rows(&padded), vec![]);
+        assert_eq!(row_spans(&padded), row_spans(&circle));
+    }
+}
+
+#[test]
+fn rendering_ignores_trailing_newlines() {
+    for (circle, padded) in with_trailing_newlines() {
+        assert_eq!(render_svg(&padded, 4), render_svg(&circle, 4));
+    }
+    
+    let circle = generate_circle(Radius(2), '#', '.').unwrap();
+    let svg = render_svg(&format!("{circle}\n\n"), 4).unwrap();
+    
+    assert_eq!(svg.matches("<rect").count(), foreground_count(&circle).unwrap());
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn png_rendering_ignores_trailing_newlines() {
+    for (circle, padded) in with_trailing_newlines() {
+        assert_eq!(render_png(&padded, 3), render_png(&circle, 3));
+    }
+}
@@ -0,0 +1,44 @@
+mod common;
+
+use common::edit;
+use text_circle_validator::*;
+
+#[test]
+fn multi_byte_characters_are_counted_once_when_checking_the_square() {
+    let circle = generate_circle(Radius(4), '●', '.').unwrap();
+    
+    assert_eq!(validate(&circle), ValidationResult::Valid { radius: 4, foreground: '●', background: '.' });
+    assert_eq!(validate(&circle.replace('.', "·")), ValidationResult::Valid { radius: 4, foreground: '●', background: '·' });
+}
+
+#[test]
+fn a_row_one_multi_byte_character_short_is_not_square() {
+    let circle = generate_circle(Radius(4), '●', '.').unwrap();
+    let short = circle.replacen("●.\n", "●\n", 1);
+    
+    assert!(matches!(validate(&short), ValidationResult::NotSquare { .. }), "{short}");
+}
+
+#[test]
+fn byte_offsets_land_on_multi_byte_characters() {
+    let circle = generate_circle(Radius(4), '●', '.').unwrap();
+    let stray = edit(&circle, 3, 4, '●').replace('\n', "\r\n");
+    let result = validate(&stray);
+    let offsets = result.byte_offsets(&stray);
+    
+    assert_eq!(result, ValidationResult::MissingBackground { background: '.', positions: vec![(3, 4)] });
+    assert_eq!(offsets, vec![byte_offset(&stray, 3, 4).unwrap()]);
+    assert!(stray[offsets[0]..].starts_with("●."));
+    assert!(stray[..offsets[0]].ends_with("\r\n●.."));
+}
+
+#[test]
+fn a_variation_selector_emoji_is_diagnosed() {
+    let hearts = generate_circle(Radius(3), 'X', ' ').unwrap().replace('X', "\u{2764}\u{FE0F}");
+    let found = combining_characters(&hearts);
+    
+    assert!(!validate(&hearts).is_valid());
+    assert_eq!(found[0], CombiningCharacter { character: '\u{FE0F}', combines_with: Some('\u{2764}'), position: (2, 0) });
+    assert_eq!(found[0].to_string(), "The character '\\u{fe0f}' at (x, y) (2, 0) combines with '❤' to look like a single cell, but counts as 2. Use a character that is a single scalar value instead.");
+    assert!(combining_characters(&generate_circle(Radius(3), '#', '.').unwrap()).is_empty());
+}
@@ -0,0 +1,26 @@
+#![cfg(all(target_arch = "wasm32", feature = "serde"))]
+
+mod common;
+
+use common::{edit, THIN_RING};
+use text_circle_validator::*;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn structured_results_of_invalid_circles_have_every_field() {
+    let structured = validate_structured(&edit(THIN_RING, 7, 1, '.'));
+    let object: serde_json::Value = serde_wasm_bindgen::from_value(structured).unwrap();
+    
+    assert_eq!(object["valid"], false);
+    assert_eq!(object["reason"], "escape_path");
+    assert_eq!(object["positions"][0], serde_json::json!([4, 4]));
+    assert_eq!(object["diagram"].as_array().unwrap().len(), 9);
+}
+
+#[wasm_bindgen_test]
+fn deviation_cells_come_in_threes() {
+    let cells = deviation_cells(&edit(THIN_RING, 3, 3, '#'));
+    
+    assert!(!cells.is_empty());
+    assert_eq!(cells.len() % 3, 0);
+}